@@ -0,0 +1,370 @@
+extern crate blackjack;
+
+use std::env;
+use std::process;
+
+use blackjack::cards::{Deck, Rank};
+use blackjack::composition::Composition;
+use blackjack::dealer::dealer_outcomes;
+use blackjack::i18n::Language;
+
+/// The dealer's upcards, in the order they are shown in tables.
+const UPCARDS: [Rank; 10] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Ace,
+];
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let locale_language = locale_language();
+    let language = language(&args, locale_language).unwrap_or_else(|code| {
+        eprintln!("{}", unsupported_language(locale_language, &code));
+        process::exit(2);
+    });
+
+    // Options such as `--lang` may come first when there is no subcommand.
+    match args.first().map(String::as_str) {
+        None => print_shuffled_deck(language),
+        Some(arg) if arg.starts_with('-') => print_shuffled_deck(language),
+        Some("dealer-outcomes") => print_dealer_outcomes(&args, language),
+        Some("shuffle-test") => print_shuffle_test(&args, language),
+        Some(command) => {
+            eprintln!("{}", unknown_command(language, command));
+            eprintln!("{}", usage(language));
+            process::exit(2);
+        }
+    }
+}
+
+fn print_shuffled_deck(language: Language) {
+    // Doesn't play a game yet.
+    // Just prints out a shuffled deck.
+    print!("{}: ", shuffled_deck_label(language));
+    let mut deck = Deck::shuffled();
+    while let Some(card) = deck.pop() {
+        print!("{} ", card)
+    }
+    println!();
+}
+
+/// Prints the dealer's bust probability and final-total distribution for
+/// each upcard, for the number of decks given with `--decks` (six by default)
+/// and with the dealer hitting soft 17 if `--h17` is given.
+fn print_dealer_outcomes(args: &[String], language: Language) {
+    let deck_count = count_option(args, "--decks")
+        .unwrap_or_else(|value| {
+            eprintln!("{}", invalid_deck_count(language, &value));
+            process::exit(2);
+        })
+        .unwrap_or(6);
+    let hits_soft_17 = args.iter().any(|arg| arg == "--h17");
+
+    println!(
+        "{}, {}",
+        dealer_outcomes_heading(language, deck_count),
+        if hits_soft_17 { "H17" } else { "S17" }
+    );
+    let (upcard, bust) = dealer_outcomes_columns(language);
+    println!(
+        "{:<6}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}",
+        upcard, "17", "18", "19", "20", "21", "BJ", bust
+    );
+    for &upcard in UPCARDS.iter() {
+        let mut composition = Composition::new(deck_count);
+        composition.remove(upcard);
+        let outcomes = dealer_outcomes(upcard, &composition, hits_soft_17);
+        print!("{:<6}", upcard.symbol());
+        for total in 17..=21 {
+            print!("{:>7.2}%", outcomes.total(total) * 100.0);
+        }
+        print!("{:>7.2}%", outcomes.blackjack() * 100.0);
+        println!("{:>7.2}%", outcomes.bust() * 100.0);
+    }
+}
+
+/// Shuffles a deck the number of times given with `--shuffles` (100,000 by
+/// default) and prints a chi-squared test of whether every card is equally
+/// likely to end up in every position.
+fn print_shuffle_test(args: &[String], language: Language) {
+    let shuffles = count_option(args, "--shuffles")
+        .unwrap_or_else(|value| {
+            eprintln!("{}", invalid_shuffle_count(language, &value));
+            process::exit(2);
+        })
+        .unwrap_or(100_000);
+
+    // counts[card][position]
+    let mut counts = [[0u32; 52]; 52];
+    for _ in 0..shuffles {
+        let mut deck = Deck::default();
+        deck.shuffle();
+        for (position, card) in deck.iter().enumerate() {
+            counts[card.index()][position] += 1;
+        }
+    }
+
+    let expected = f64::from(shuffles) / 52.0;
+    let chi_squared: f64 = counts
+        .iter()
+        .flat_map(|positions| positions.iter())
+        .map(|&count| (f64::from(count) - expected).powi(2) / expected)
+        .sum();
+    // Each card's positions add up to the number of shuffles, and so do each
+    // position's cards.
+    let degrees_of_freedom = 51.0 * 51.0;
+    let z = (chi_squared - degrees_of_freedom) / (2.0 * degrees_of_freedom).sqrt();
+
+    println!("{}", shuffle_test_heading(language, shuffles));
+    println!(
+        "{}",
+        chi_squared_summary(language, chi_squared, degrees_of_freedom, z)
+    );
+    println!("{}", shuffle_test_verdict(language, z.abs() > 3.0));
+}
+
+/// Returns the value of an option given as `name value` or `name=value`.
+fn option_value(args: &[String], name: &str) -> Option<String> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next().cloned();
+        }
+        if let Some(value) = arg
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(String::from(value));
+        }
+    }
+    None
+}
+
+/// Returns the count given with option `name`, or `None` if the option isn't
+/// given. Returns the option's value as an error if it isn't a whole number of
+/// at least 1.
+fn count_option(args: &[String], name: &str) -> Result<Option<u32>, String> {
+    match option_value(args, name) {
+        Some(value) => match value.parse() {
+            Ok(count) if count >= 1 => Ok(Some(count)),
+            _ => Err(value),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Returns the language given with `--lang`, or else `default`. Returns the
+/// code given with `--lang` as an error if it isn't a supported language.
+fn language(args: &[String], default: Language) -> Result<Language, String> {
+    match option_value(args, "--lang") {
+        Some(code) => Language::from_code(&code).ok_or(code),
+        None => Ok(default),
+    }
+}
+
+/// Returns the language named by the locale environment variables, or else
+/// English.
+fn locale_language() -> Language {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find_map(|locale| Language::from_code(&locale))
+        .unwrap_or_default()
+}
+
+fn usage(language: Language) -> String {
+    let label = match language {
+        Language::English => "usage:",
+        Language::Spanish => "uso:",
+        Language::German => "Aufruf:",
+        Language::French => "usage :",
+    };
+    format!(
+        "{} blackjack [dealer-outcomes [--decks N] [--h17] | shuffle-test [--shuffles N]] [--lang CODE]",
+        label
+    )
+}
+
+fn unknown_command(language: Language, command: &str) -> String {
+    match language {
+        Language::English => format!("unknown command: {}", command),
+        Language::Spanish => format!("orden desconocida: {}", command),
+        Language::German => format!("unbekannter Befehl: {}", command),
+        Language::French => format!("commande inconnue : {}", command),
+    }
+}
+
+fn unsupported_language(language: Language, code: &str) -> String {
+    match language {
+        Language::English => format!("unsupported language: {}", code),
+        Language::Spanish => format!("idioma no admitido: {}", code),
+        Language::German => format!("nicht unterstützte Sprache: {}", code),
+        Language::French => format!("langue non prise en charge : {}", code),
+    }
+}
+
+fn invalid_deck_count(language: Language, value: &str) -> String {
+    match language {
+        Language::English => format!("invalid deck count: {}", value),
+        Language::Spanish => format!("número de barajas no válido: {}", value),
+        Language::German => format!("ungültige Anzahl Decks: {}", value),
+        Language::French => format!("nombre de jeux invalide : {}", value),
+    }
+}
+
+fn invalid_shuffle_count(language: Language, value: &str) -> String {
+    match language {
+        Language::English => format!("invalid shuffle count: {}", value),
+        Language::Spanish => format!("número de barajadas no válido: {}", value),
+        Language::German => format!("ungültige Anzahl Mischvorgänge: {}", value),
+        Language::French => format!("nombre de mélanges invalide : {}", value),
+    }
+}
+
+fn shuffled_deck_label(language: Language) -> &'static str {
+    match language {
+        Language::English => "Shuffled deck",
+        Language::Spanish => "Baraja barajada",
+        Language::German => "Gemischtes Kartenspiel",
+        Language::French => "Jeu mélangé",
+    }
+}
+
+fn dealer_outcomes_heading(language: Language, deck_count: u32) -> String {
+    let one = deck_count == 1;
+    match language {
+        Language::English => format!(
+            "Dealer outcomes, {} {}",
+            deck_count,
+            if one { "deck" } else { "decks" }
+        ),
+        Language::Spanish => format!(
+            "Resultados del crupier, {} {}",
+            deck_count,
+            if one { "baraja" } else { "barajas" }
+        ),
+        Language::German => format!(
+            "Ergebnisse des Gebers, {} {}",
+            deck_count,
+            if one { "Deck" } else { "Decks" }
+        ),
+        Language::French => format!(
+            "Résultats du croupier, {} {}",
+            deck_count,
+            if one { "jeu" } else { "jeux" }
+        ),
+    }
+}
+
+/// Returns the headings of the upcard and bust columns.
+fn dealer_outcomes_columns(language: Language) -> (&'static str, &'static str) {
+    match language {
+        Language::English => ("Upcard", "Bust"),
+        Language::Spanish => ("Carta", "Se pasa"),
+        Language::German => ("Karte", "Über"),
+        Language::French => ("Carte", "Sauté"),
+    }
+}
+
+fn shuffle_test_heading(language: Language, shuffles: u32) -> String {
+    match language {
+        Language::English => format!("Card positions after {} shuffles", shuffles),
+        Language::Spanish => format!("Posiciones de las cartas tras {} barajadas", shuffles),
+        Language::German => format!("Kartenpositionen nach {} Mischvorgängen", shuffles),
+        Language::French => format!("Positions des cartes après {} mélanges", shuffles),
+    }
+}
+
+fn chi_squared_summary(language: Language, chi_squared: f64, degrees: f64, z: f64) -> String {
+    match language {
+        Language::English => format!(
+            "chi-squared {:.1} with {} degrees of freedom (z = {:.2})",
+            chi_squared, degrees, z
+        ),
+        Language::Spanish => format!(
+            "chi cuadrado {:.1} con {} grados de libertad (z = {:.2})",
+            chi_squared, degrees, z
+        ),
+        Language::German => format!(
+            "Chi-Quadrat {:.1} mit {} Freiheitsgraden (z = {:.2})",
+            chi_squared, degrees, z
+        ),
+        Language::French => format!(
+            "khi carré {:.1} avec {} degrés de liberté (z = {:.2})",
+            chi_squared, degrees, z
+        ),
+    }
+}
+
+fn shuffle_test_verdict(language: Language, biased: bool) -> &'static str {
+    match (language, biased) {
+        (Language::English, true) => "The shuffle looks biased.",
+        (Language::English, false) => "No bias detected.",
+        (Language::Spanish, true) => "La barajada parece sesgada.",
+        (Language::Spanish, false) => "No se detecta sesgo.",
+        (Language::German, true) => "Das Mischen wirkt verzerrt.",
+        (Language::German, false) => "Keine Verzerrung festgestellt.",
+        (Language::French, true) => "Le mélange semble biaisé.",
+        (Language::French, false) => "Aucun biais détecté.",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn option_values() {
+        let args = args("dealer-outcomes --decks 2 --h17 --lang=fr");
+        assert_eq!(option_value(&args, "--decks"), Some(String::from("2")));
+        assert_eq!(option_value(&args, "--lang"), Some(String::from("fr")));
+        assert_eq!(option_value(&args, "--shuffles"), None);
+        assert_eq!(
+            option_value(&args, "--h17"),
+            Some(String::from("--lang=fr"))
+        );
+    }
+
+    #[test]
+    fn count_options() {
+        assert_eq!(count_option(&args("--decks 8"), "--decks"), Ok(Some(8)));
+        assert_eq!(count_option(&args("--h17"), "--decks"), Ok(None));
+        assert_eq!(
+            count_option(&args("--decks=0"), "--decks"),
+            Err(String::from("0"))
+        );
+        assert_eq!(
+            count_option(&args("--decks six"), "--decks"),
+            Err(String::from("six"))
+        );
+    }
+
+    #[test]
+    fn language_selection() {
+        let default = Language::German;
+        assert_eq!(language(&args("--lang es"), default), Ok(Language::Spanish));
+        assert_eq!(
+            language(&args("shuffle-test --lang=fr_FR"), default),
+            Ok(Language::French)
+        );
+        assert_eq!(
+            language(&args("shuffle-test"), default),
+            Ok(Language::German)
+        );
+        assert_eq!(
+            language(&args("--lang ja"), default),
+            Err(String::from("ja"))
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Bankroll risk calculations.
+//!
+//! Requires the `std` feature, since the calculations need floating-point
+//! functions that `core` doesn't provide.
+
+/// Returns the probability of growing `bankroll` to `goal` before losing it
+/// all, when each hand wins `ev` and has a standard deviation of `sd`, both
+/// in the same units as the bankroll.
+///
+/// The result is the gambler's-ruin approximation that treats the bankroll as
+/// drifting continuously, which is close for bankrolls of many bets. `ev` and
+/// `sd` should be measured per hand for the bet spread and rules in use.
+///
+/// Examples:
+///
+/// ```
+/// use blackjack::bankroll::goal_probability;
+///
+/// // With no edge, the chance of doubling is one half.
+/// assert!((goal_probability(500.0, 1000.0, 0.0, 1.15) - 0.5).abs() < 1e-12);
+///
+/// // A half percent edge makes doubling 500 units a good bet.
+/// let p = goal_probability(500.0, 1000.0, 0.005, 1.15);
+/// assert!(p > 0.97 && p < 0.98);
+/// ```
+pub fn goal_probability(bankroll: f64, goal: f64, ev: f64, sd: f64) -> f64 {
+    if bankroll <= 0.0 {
+        return 0.0;
+    }
+    if bankroll >= goal {
+        return 1.0;
+    }
+    let drift = 2.0 * ev / (sd * sd);
+    if drift == 0.0 {
+        return bankroll / goal;
+    }
+    (-drift * bankroll).exp_m1() / (-drift * goal).exp_m1()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goal_probability_follows_the_edge() {
+        let even = goal_probability(200.0, 1000.0, 0.0, 1.1);
+        assert!((even - 0.2).abs() < 1e-12);
+        assert!(goal_probability(200.0, 1000.0, 0.005, 1.1) > even);
+        assert!(goal_probability(200.0, 1000.0, -0.005, 1.1) < even);
+        assert_eq!(goal_probability(0.0, 1000.0, 0.01, 1.1), 0.0);
+        assert_eq!(goal_probability(1000.0, 1000.0, -0.01, 1.1), 1.0);
+    }
+}
@@ -2,14 +2,22 @@
 //!
 //! There is nothing specific to the rules of Blackjack in this module. It can
 //! be reused as-is for other card games.
+//!
+//! The module only needs `core` and `alloc`. Shuffling with the thread-local
+//! random number generator requires the default `std` feature.
 
 extern crate rand;
 
+use alloc::vec::Vec;
+
 use rand::seq::SliceRandom;
+#[cfg(feature = "std")]
 use rand::thread_rng;
+use rand::Rng;
 
-use std::fmt;
-use std::ops::Index;
+use core::fmt;
+use core::ops::Index;
+use core::str::FromStr;
 
 /// A card's suit.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -44,6 +52,20 @@ impl Suit {
             Suit::Spades => "\u{2660}",
         }
     }
+
+    /// Parses a suit symbol or letter from the start of `s`, returning the suit
+    /// and the remainder of the string.
+    pub(crate) fn parse_prefix(s: &str) -> Option<(Suit, &str)> {
+        let mut chars = s.chars();
+        let suit = match chars.next()? {
+            '\u{2663}' | 'C' | 'c' => Suit::Clubs,
+            '\u{2666}' | 'D' | 'd' => Suit::Diamonds,
+            '\u{2665}' | 'H' | 'h' => Suit::Hearts,
+            '\u{2660}' | 'S' | 's' => Suit::Spades,
+            _ => return None,
+        };
+        Some((suit, chars.as_str()))
+    }
 }
 
 impl fmt::Display for Suit {
@@ -52,6 +74,29 @@ impl fmt::Display for Suit {
     }
 }
 
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    /// Parses a suit from its symbol or from its initial letter, in either
+    /// case.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Suit;
+    ///
+    /// assert_eq!("♠".parse(), Ok(Suit::Spades));
+    /// assert_eq!("h".parse(), Ok(Suit::Hearts));
+    /// assert!("x".parse::<Suit>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Suit, ParseCardError> {
+        match Suit::parse_prefix(s) {
+            Some((suit, "")) => Ok(suit),
+            _ => Err(ParseCardError(())),
+        }
+    }
+}
+
 /// A card's rank.
 #[derive(Debug, PartialOrd, Ord, PartialEq, Eq, Clone, Copy)]
 pub enum Rank {
@@ -87,6 +132,34 @@ pub const ALL_RANKS: [Rank; 13] = [
     Rank::Ace,
 ];
 
+/// The ranks of a Spanish deck: every rank except `Ten`.
+pub const SPANISH_RANKS: [Rank; 12] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+/// The ranks of a Piquet deck: `Seven` through `Ace`.
+pub const PIQUET_RANKS: [Rank; 8] = [
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
 impl Rank {
     /// Returns a single-character symbol for the rank.
     ///
@@ -121,6 +194,58 @@ impl Rank {
             Rank::King => "K",
         }
     }
+
+    /// Parses a rank symbol from the start of `s`, returning the rank and the
+    /// remainder of the string.
+    pub(crate) fn parse_prefix(s: &str) -> Option<(Rank, &str)> {
+        if let Some(rest) = s.strip_prefix("10") {
+            return Some((Rank::Ten, rest));
+        }
+        let mut chars = s.chars();
+        let rank = match chars.next()? {
+            'A' | 'a' => Rank::Ace,
+            '2' => Rank::Two,
+            '3' => Rank::Three,
+            '4' => Rank::Four,
+            '5' => Rank::Five,
+            '6' => Rank::Six,
+            '7' => Rank::Seven,
+            '8' => Rank::Eight,
+            '9' => Rank::Nine,
+            'T' | 't' => Rank::Ten,
+            'J' | 'j' => Rank::Jack,
+            'Q' | 'q' => Rank::Queen,
+            'K' | 'k' => Rank::King,
+            _ => return None,
+        };
+        Some((rank, chars.as_str()))
+    }
+}
+
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    /// Parses a rank from its symbol, in either case. A ten may also be
+    /// written as "10".
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank;
+    ///
+    /// assert_eq!("A".parse(), Ok(Rank::Ace));
+    /// assert_eq!("7".parse(), Ok(Rank::Seven));
+    /// assert_eq!("T".parse(), Ok(Rank::Ten));
+    /// assert_eq!("10".parse(), Ok(Rank::Ten));
+    /// assert_eq!("q".parse(), Ok(Rank::Queen));
+    /// assert!("1".parse::<Rank>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Rank, ParseCardError> {
+        match Rank::parse_prefix(s) {
+            Some((rank, "")) => Ok(rank),
+            _ => Err(ParseCardError(())),
+        }
+    }
 }
 
 /// A playing card, with a rank and suit.
@@ -140,6 +265,23 @@ impl Card {
     pub fn suit(self) -> Suit {
         self.suit
     }
+
+    /// Returns a distinct index in `0..52` for each card, for use in tables
+    /// indexed by card.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// assert_eq!(card(Two, Clubs).index(), 0);
+    /// assert_eq!(card(Ace, Spades).index(), 51);
+    /// ```
+    pub fn index(self) -> usize {
+        self.suit as usize * 13 + (self.rank as usize - 2)
+    }
 }
 
 impl fmt::Display for Card {
@@ -148,6 +290,44 @@ impl fmt::Display for Card {
     }
 }
 
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    /// Parses a card written as a rank followed by a suit, such as "A♠", "Th",
+    /// or "10D".
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// assert_eq!("A♠".parse(), Ok(card(Ace, Spades)));
+    /// assert_eq!("Th".parse(), Ok(card(Ten, Hearts)));
+    /// assert_eq!("10D".parse(), Ok(card(Ten, Diamonds)));
+    /// assert!("A".parse::<blackjack::cards::Card>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Card, ParseCardError> {
+        let (rank, rest) = Rank::parse_prefix(s).ok_or(ParseCardError(()))?;
+        let suit = rest.parse()?;
+        Ok(card(rank, suit))
+    }
+}
+
+/// The error returned when a `Rank`, `Suit`, or `Card` can't be parsed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseCardError(());
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid card notation")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseCardError {}
+
 /// Creates a `Card` with specified rank and suit.
 ///
 /// Examples:
@@ -170,17 +350,14 @@ pub fn card(rank: Rank, suit: Suit) -> Card {
 }
 
 /// A `Hand` is a set of cards held by a player.
-#[derive(Debug)]
+///
+/// Two hands are equal if they hold the same cards in the same order. Use
+/// `same_cards` to ignore the order.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub struct Hand {
     cards: Vec<Card>,
 }
 
-impl Default for Hand {
-    fn default() -> Self {
-        Hand { cards: vec![] }
-    }
-}
-
 impl Index<usize> for Hand {
     type Output = Card;
 
@@ -190,6 +367,14 @@ impl Index<usize> for Hand {
 }
 
 impl Hand {
+    /// Returns an empty hand with room for `capacity` cards before it needs to
+    /// reallocate.
+    pub fn with_capacity(capacity: usize) -> Hand {
+        Hand {
+            cards: Vec::with_capacity(capacity),
+        }
+    }
+
     /// Returns the count of cards in the hand.
     pub fn len(&self) -> usize {
         self.cards.len()
@@ -200,17 +385,157 @@ impl Hand {
         self.cards.is_empty()
     }
 
+    /// Returns an iterator over the cards in the order they were added.
+    pub fn iter(&self) -> core::slice::Iter<'_, Card> {
+        self.cards.iter()
+    }
+
     /// Adds a card to the hand.
     pub fn push(&mut self, card: Card) {
         self.cards.push(card);
     }
+
+    /// Removes and returns the card at `index`, shifting the later cards down.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Card {
+        self.cards.remove(index)
+    }
+
+    /// Removes and returns the most recently added card, or `None` if the hand
+    /// is empty.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, Hand};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// // Splitting a pair of eights.
+    /// let mut hand = Hand::default();
+    /// hand.push(card(Eight, Clubs));
+    /// hand.push(card(Eight, Hearts));
+    ///
+    /// let mut split_hand = Hand::default();
+    /// split_hand.push(hand.take_last().unwrap());
+    /// assert_eq!(hand.len(), 1);
+    /// assert_eq!(split_hand[0], card(Eight, Hearts));
+    /// ```
+    pub fn take_last(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    /// Moves every card in the hand to the end of `other`, in order, leaving
+    /// this hand empty.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, Hand};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// let mut hand = Hand::default();
+    /// hand.push(card(Two, Clubs));
+    /// hand.push(card(Three, Clubs));
+    ///
+    /// let mut discards = Hand::default();
+    /// discards.push(card(Ace, Spades));
+    /// hand.drain_to(&mut discards);
+    ///
+    /// assert!(hand.is_empty());
+    /// assert_eq!(discards.len(), 3);
+    /// assert_eq!(discards[2], card(Three, Clubs));
+    /// ```
+    pub fn drain_to(&mut self, other: &mut Hand) {
+        other.cards.append(&mut self.cards);
+    }
+
+    /// Removes all cards from the hand.
+    ///
+    /// The hand keeps its allocated storage, so it can be reused for the next
+    /// round without allocating again.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Deck;
+    ///
+    /// let mut deck = Deck::default();
+    /// let mut hands = deck.deal_hands(1, 2);
+    /// let hand = &mut hands[0];
+    /// assert_eq!(hand.len(), 2);
+    ///
+    /// hand.clear();
+    /// assert!(hand.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.cards.clear();
+    }
+
+    /// Sorts the cards into the given order.
+    pub fn sort(&mut self, order: CardOrder) {
+        order.sort(&mut self.cards);
+    }
+
+    /// Returns `true` if both hands hold the same cards, in any order.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, Hand};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// let mut hand1 = Hand::default();
+    /// hand1.push(card(Ace, Spades));
+    /// hand1.push(card(Ten, Hearts));
+    ///
+    /// let mut hand2 = Hand::default();
+    /// hand2.push(card(Ten, Hearts));
+    /// hand2.push(card(Ace, Spades));
+    ///
+    /// assert_ne!(hand1, hand2);
+    /// assert!(hand1.same_cards(&hand2));
+    /// ```
+    pub fn same_cards(&self, other: &Hand) -> bool {
+        same_cards(&self.cards, &other.cards)
+    }
+
+    /// Returns the hand with its cards sorted into the given order.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, CardOrder, Hand};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// let mut hand = Hand::default();
+    /// hand.push(card(King, Hearts));
+    /// hand.push(card(Two, Spades));
+    /// hand.push(card(King, Clubs));
+    ///
+    /// let hand = hand.sorted(CardOrder::SuitWithinRank);
+    /// assert_eq!(hand[0], card(Two, Spades));
+    /// assert_eq!(hand[1], card(King, Clubs));
+    /// assert_eq!(hand[2], card(King, Hearts));
+    /// ```
+    pub fn sorted(mut self, order: CardOrder) -> Hand {
+        self.sort(order);
+        self
+    }
 }
 
 /// A collection of cards.
 ///
 /// The cards are ordered from the bottom to the top of the deck.  So, drawing a
 /// card is taking one off the end.
-#[derive(Debug)]
+///
+/// Two decks are equal if they hold the same cards in the same order. Use
+/// `same_cards` to ignore the order.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Deck {
     cards: Vec<Card>,
 }
@@ -242,17 +567,75 @@ impl Default for Deck {
     /// assert_eq!(deck[51], card(Ace, Spades));
     /// ```
     fn default() -> Self {
-        let mut cards = vec![];
+        Deck::with_ranks(&ALL_RANKS)
+    }
+}
+
+impl Deck {
+    /// Returns an ordered deck holding one card of each suit for each of the
+    /// given ranks.
+    ///
+    /// This makes stripped decks like the ones some games use. Within each
+    /// suit, the cards are in the order that `ranks` lists them.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, Deck};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// let deck = Deck::with_ranks(&[Nine, Ten, Jack, Queen, King, Ace]);
+    /// assert_eq!(deck.len(), 24);
+    /// assert_eq!(deck[0], card(Nine, Clubs));
+    /// assert_eq!(deck[23], card(Ace, Spades));
+    /// ```
+    pub fn with_ranks(ranks: &[Rank]) -> Deck {
+        let mut cards = Vec::with_capacity(ranks.len() * ALL_SUITS.len());
         for &suit in ALL_SUITS.iter() {
-            for &rank in ALL_RANKS.iter() {
+            for &rank in ranks {
                 cards.push(card(rank, suit));
             }
         }
         Deck { cards }
     }
-}
 
-impl Deck {
+    /// Returns an ordered 48-card Spanish deck, which has no tens.
+    ///
+    /// The jacks, queens, and kings remain, so this is the deck used for
+    /// games like Spanish 21.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Deck;
+    /// use blackjack::cards::Rank::*;
+    ///
+    /// let deck = Deck::spanish();
+    /// assert_eq!(deck.len(), 48);
+    /// assert!(deck.iter().all(|card| card.rank() != Ten));
+    /// ```
+    pub fn spanish() -> Deck {
+        Deck::with_ranks(&SPANISH_RANKS)
+    }
+
+    /// Returns an ordered 32-card Piquet deck, which has only the sevens
+    /// through aces.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Deck;
+    /// use blackjack::cards::Rank::*;
+    ///
+    /// let deck = Deck::piquet();
+    /// assert_eq!(deck.len(), 32);
+    /// assert!(deck.iter().all(|card| card.rank() >= Seven));
+    /// ```
+    pub fn piquet() -> Deck {
+        Deck::with_ranks(&PIQUET_RANKS)
+    }
+
     /// Returns a shuffled deck of 52 cards.
     ///
     /// Examples:
@@ -263,6 +646,7 @@ impl Deck {
     /// let deck = Deck::shuffled();
     /// assert_eq!(deck.len(), 52);
     /// ```
+    #[cfg(feature = "std")]
     pub fn shuffled() -> Deck {
         let mut deck = Deck::default();
         deck.shuffle();
@@ -270,8 +654,127 @@ impl Deck {
     }
 
     /// Shuffles the cards.
+    #[cfg(feature = "std")]
     pub fn shuffle(&mut self) {
-        self.cards.shuffle(&mut thread_rng());
+        self.shuffle_with(&mut RngShuffler::new(thread_rng()));
+    }
+
+    /// Shuffles the cards with the given `Shuffler`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{Deck, RngShuffler};
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// // Decks shuffled from the same seed come out in the same order.
+    /// let mut deck1 = Deck::default();
+    /// deck1.shuffle_with(&mut RngShuffler::new(StdRng::seed_from_u64(21)));
+    /// let mut deck2 = Deck::default();
+    /// deck2.shuffle_with(&mut RngShuffler::new(StdRng::seed_from_u64(21)));
+    /// assert!(deck1.iter().eq(deck2.iter()));
+    /// ```
+    pub fn shuffle_with<S: Shuffler + ?Sized>(&mut self, shuffler: &mut S) {
+        shuffler.shuffle(&mut self.cards);
+    }
+
+    /// Returns `true` if both decks hold the same cards, in any order.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Deck;
+    ///
+    /// let deck = Deck::shuffled();
+    /// assert!(deck.same_cards(&Deck::default()));
+    ///
+    /// let mut short_deck = Deck::default();
+    /// short_deck.pop();
+    /// assert!(!deck.same_cards(&short_deck));
+    /// ```
+    pub fn same_cards(&self, other: &Deck) -> bool {
+        same_cards(&self.cards, &other.cards)
+    }
+
+    /// Sorts the deck into the given order, from the bottom of the deck to the
+    /// top.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, CardOrder, Deck};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// let mut deck = Deck::shuffled();
+    /// deck.sort(CardOrder::NewDeck);
+    /// assert_eq!(deck[0], card(Ace, Spades));
+    /// assert_eq!(deck[12], card(King, Spades));
+    /// assert_eq!(deck[13], card(Ace, Diamonds));
+    /// assert_eq!(deck[26], card(King, Clubs));
+    /// assert_eq!(deck[39], card(King, Hearts));
+    /// assert_eq!(deck[51], card(Ace, Hearts));
+    ///
+    /// // A sorted deck is back in the order of `Deck::default()`.
+    /// deck.sort(CardOrder::RankWithinSuit);
+    /// assert!(deck.iter().eq(Deck::default().iter()));
+    /// ```
+    pub fn sort(&mut self, order: CardOrder) {
+        order.sort(&mut self.cards);
+    }
+
+    /// Cuts the deck, leaving `index` cards in the bottom packet.
+    ///
+    /// The top `len() - index` cards are lifted off and placed underneath the
+    /// others, so the card at `index` becomes the bottom card. Cutting at `0`
+    /// or at `len()` leaves the deck unchanged.
+    ///
+    /// Panics if `index` is greater than `len()`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, Deck};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// let mut deck = Deck::default();
+    /// deck.cut(13);
+    /// assert_eq!(deck.len(), 52);
+    /// assert_eq!(deck[0], card(Two, Diamonds));
+    /// assert_eq!(deck[38], card(Ace, Spades));
+    /// assert_eq!(deck[39], card(Two, Clubs));
+    /// assert_eq!(deck[51], card(Ace, Clubs));
+    /// ```
+    pub fn cut(&mut self, index: usize) {
+        self.cards.rotate_left(index);
+    }
+
+    /// Cuts the deck at a random position chosen by `rng`, and returns the
+    /// `index` that was passed to `cut`.
+    ///
+    /// Like a player's cut, both packets are left with at least one card, so a
+    /// deck of two or more cards always changes order.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Deck;
+    ///
+    /// let mut deck = Deck::default();
+    /// let index = deck.cut_random(&mut rand::thread_rng());
+    /// assert!(index >= 1 && index <= 51);
+    /// assert_eq!(deck.len(), 52);
+    /// ```
+    pub fn cut_random<R: Rng + ?Sized>(&mut self, rng: &mut R) -> usize {
+        let index = if self.cards.len() < 2 {
+            0
+        } else {
+            rng.gen_range(1, self.cards.len())
+        };
+        self.cut(index);
+        index
     }
 
     /// Returns count of cards remaining in the deck.
@@ -284,6 +787,12 @@ impl Deck {
         self.cards.is_empty()
     }
 
+    /// Returns an iterator over the cards, from the bottom of the deck to the
+    /// top.
+    pub fn iter(&self) -> core::slice::Iter<'_, Card> {
+        self.cards.iter()
+    }
+
     /// Removes the top card from the deck and returns it, or `None` if no cards
     /// remain.
     ///
@@ -345,14 +854,54 @@ impl Deck {
     /// assert_eq!(hands[2][1], card(Nine, Spades));
     /// ```
     pub fn deal_hands(&mut self, hand_count: u32, cards_per_hand: u32) -> Vec<Hand> {
-        let mut hands = vec![];
+        self.deal_hands_with_style(hand_count, cards_per_hand, DealStyle::RoundRobin)
+    }
+
+    /// Deal the specified number of hands from a deck, in the given style.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, DealStyle, Deck};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    ///
+    /// let mut deck = Deck::default();
+    /// let hands = deck.deal_hands_with_style(2, 3, DealStyle::Block);
+    ///
+    /// assert_eq!(hands[0][0], card(Ace, Spades));
+    /// assert_eq!(hands[0][1], card(King, Spades));
+    /// assert_eq!(hands[0][2], card(Queen, Spades));
+    ///
+    /// assert_eq!(hands[1][0], card(Jack, Spades));
+    /// assert_eq!(hands[1][1], card(Ten, Spades));
+    /// assert_eq!(hands[1][2], card(Nine, Spades));
+    /// ```
+    pub fn deal_hands_with_style(
+        &mut self,
+        hand_count: u32,
+        cards_per_hand: u32,
+        style: DealStyle,
+    ) -> Vec<Hand> {
+        let mut hands = Vec::with_capacity(hand_count as usize);
         for _ in 0..hand_count {
-            hands.push(Hand::default())
+            hands.push(Hand::with_capacity(cards_per_hand as usize))
         }
 
-        for _ in 0..cards_per_hand {
-            for hand in hands.iter_mut() {
-                hand.push(self.pop().expect("unable to draw card from deck"))
+        match style {
+            DealStyle::RoundRobin => {
+                for _ in 0..cards_per_hand {
+                    for hand in hands.iter_mut() {
+                        hand.push(self.pop().expect("unable to draw card from deck"))
+                    }
+                }
+            }
+            DealStyle::Block => {
+                for hand in hands.iter_mut() {
+                    for _ in 0..cards_per_hand {
+                        hand.push(self.pop().expect("unable to draw card from deck"))
+                    }
+                }
             }
         }
 
@@ -360,8 +909,110 @@ impl Deck {
     }
 }
 
+/// The order in which `Deck::deal_hands_with_style` deals cards to hands.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DealStyle {
+    /// One card to each hand in turn, going around until every hand is full.
+    RoundRobin,
+    /// All of the first hand's cards, then all of the second hand's, and so
+    /// on.
+    Block,
+}
+
+/// Returns `true` if `a` and `b` hold the same numbers of each card.
+fn same_cards(a: &[Card], b: &[Card]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut counts = [0isize; 52];
+    for card in a {
+        counts[card.index()] += 1;
+    }
+    for card in b {
+        counts[card.index()] -= 1;
+    }
+    counts.iter().all(|&count| count == 0)
+}
+
+/// Orders in which cards can be sorted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CardOrder {
+    /// Grouped by suit (clubs, diamonds, hearts, spades), then by rank from
+    /// two up to ace. This is the order of `Deck::default()`.
+    RankWithinSuit,
+    /// Grouped by rank from two up to ace, then by suit.
+    SuitWithinRank,
+    /// The order of a new deck out of the box. Face up, it runs ace to king
+    /// of spades, ace to king of diamonds, king to ace of clubs, and king to
+    /// ace of hearts, so the ace of spades is on the bottom.
+    NewDeck,
+}
+
+impl CardOrder {
+    fn sort(self, cards: &mut [Card]) {
+        cards.sort_unstable_by_key(|&card| self.key(card));
+    }
+
+    fn key(self, card: Card) -> u32 {
+        let suit_index = card.suit as u32;
+        let rank_index = card.rank as u32 - 2;
+        match self {
+            CardOrder::RankWithinSuit => suit_index * 13 + rank_index,
+            CardOrder::SuitWithinRank => rank_index * 4 + suit_index,
+            CardOrder::NewDeck => {
+                // Aces are low in new deck order.
+                let low_rank_index = (rank_index + 1) % 13;
+                match card.suit {
+                    Suit::Spades => low_rank_index,
+                    Suit::Diamonds => 13 + low_rank_index,
+                    Suit::Clubs => 26 + (12 - low_rank_index),
+                    Suit::Hearts => 39 + (12 - low_rank_index),
+                }
+            }
+        }
+    }
+}
+
+/// Something that puts cards into a random order.
+///
+/// `Deck` and `Shoe` shuffle with the thread-local random number generator by
+/// default. Implement this trait to shuffle with some other source of
+/// entropy, such as a hardware or certified RNG, or recorded entropy that is
+/// replayed.
+pub trait Shuffler {
+    /// Rearranges `cards` into a random order.
+    fn shuffle(&mut self, cards: &mut [Card]);
+}
+
+/// A `Shuffler` that does a Fisher–Yates shuffle with a random number
+/// generator.
+#[derive(Debug)]
+pub struct RngShuffler<R> {
+    rng: R,
+}
+
+impl<R: Rng> RngShuffler<R> {
+    /// Returns a shuffler that draws its randomness from `rng`.
+    pub fn new(rng: R) -> RngShuffler<R> {
+        RngShuffler { rng }
+    }
+
+    /// Returns the random number generator.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: Rng> Shuffler for RngShuffler<R> {
+    fn shuffle(&mut self, cards: &mut [Card]) {
+        cards.shuffle(&mut self.rng);
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::format;
+
     use super::Rank::*;
     use super::Suit::*;
     use super::*;
@@ -382,4 +1033,20 @@ mod tests {
             "Card { rank: Ace, suit: Spades }"
         );
     }
+
+    #[test]
+    fn custom_shuffler() {
+        struct Reverse;
+
+        impl Shuffler for Reverse {
+            fn shuffle(&mut self, cards: &mut [Card]) {
+                cards.reverse();
+            }
+        }
+
+        let mut deck = Deck::default();
+        deck.shuffle_with(&mut Reverse);
+        assert_eq!(deck[0], card(Ace, Spades));
+        assert_eq!(deck.pop(), Some(card(Two, Clubs)));
+    }
 }
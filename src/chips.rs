@@ -0,0 +1,161 @@
+//! Casino chips and making change.
+
+use core::fmt;
+
+/// A chip's value.
+#[derive(Debug, PartialOrd, Ord, PartialEq, Eq, Clone, Copy)]
+pub enum Denomination {
+    One = 1,
+    Five = 5,
+    TwentyFive = 25,
+    OneHundred = 100,
+    FiveHundred = 500,
+}
+
+/// Array of all `Denomination` values, from lowest to highest.
+pub const ALL_DENOMINATIONS: [Denomination; 5] = [
+    Denomination::One,
+    Denomination::Five,
+    Denomination::TwentyFive,
+    Denomination::OneHundred,
+    Denomination::FiveHundred,
+];
+
+impl Denomination {
+    /// Returns the chip's value in betting units.
+    pub fn value(self) -> u32 {
+        self as u32
+    }
+
+    /// Returns the color that casinos conventionally use for the chip.
+    pub fn color(self) -> &'static str {
+        match self {
+            Denomination::One => "white",
+            Denomination::Five => "red",
+            Denomination::TwentyFive => "green",
+            Denomination::OneHundred => "black",
+            Denomination::FiveHundred => "purple",
+        }
+    }
+
+    fn index(self) -> usize {
+        ALL_DENOMINATIONS
+            .iter()
+            .position(|&d| d == self)
+            .expect("denomination is in ALL_DENOMINATIONS")
+    }
+}
+
+/// A pile of chips of mixed denominations.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Chips {
+    counts: [u32; 5],
+}
+
+impl Chips {
+    /// Returns the amount made up of as few chips as possible.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::chips::{Chips, Denomination};
+    ///
+    /// let chips = Chips::breakdown(637);
+    /// assert_eq!(chips.count(Denomination::FiveHundred), 1);
+    /// assert_eq!(chips.count(Denomination::OneHundred), 1);
+    /// assert_eq!(chips.count(Denomination::TwentyFive), 1);
+    /// assert_eq!(chips.count(Denomination::Five), 2);
+    /// assert_eq!(chips.count(Denomination::One), 2);
+    /// assert_eq!(chips.len(), 7);
+    /// assert_eq!(chips.total(), 637);
+    /// ```
+    pub fn breakdown(amount: u32) -> Chips {
+        let mut chips = Chips::default();
+        let mut remaining = amount;
+        for &denomination in ALL_DENOMINATIONS.iter().rev() {
+            let count = remaining / denomination.value();
+            chips.counts[denomination.index()] = count;
+            remaining -= count * denomination.value();
+        }
+        chips
+    }
+
+    /// Returns the number of chips of the given denomination.
+    pub fn count(&self, denomination: Denomination) -> u32 {
+        self.counts[denomination.index()]
+    }
+
+    /// Adds `count` chips of the given denomination.
+    pub fn add(&mut self, denomination: Denomination, count: u32) {
+        self.counts[denomination.index()] += count;
+    }
+
+    /// Returns the total number of chips.
+    pub fn len(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns `true` if there are no chips.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total value of the chips in betting units.
+    pub fn total(&self) -> u32 {
+        ALL_DENOMINATIONS
+            .iter()
+            .map(|&d| self.count(d) * d.value())
+            .sum()
+    }
+
+    /// Returns the same value exchanged for as few chips as possible, as the
+    /// cage does when a player colors up.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::chips::{Chips, Denomination};
+    ///
+    /// let mut chips = Chips::default();
+    /// chips.add(Denomination::TwentyFive, 9);
+    /// let chips = chips.color_up();
+    /// assert_eq!(chips.count(Denomination::OneHundred), 2);
+    /// assert_eq!(chips.count(Denomination::TwentyFive), 1);
+    /// assert_eq!(chips.len(), 3);
+    /// ```
+    pub fn color_up(&self) -> Chips {
+        Chips::breakdown(self.total())
+    }
+
+    /// Returns an iterator over each denomination present and its count,
+    /// from highest to lowest, as chips are stacked for display.
+    pub fn stacks(&self) -> impl Iterator<Item = (Denomination, u32)> + '_ {
+        ALL_DENOMINATIONS
+            .iter()
+            .rev()
+            .map(move |&d| (d, self.count(d)))
+            .filter(|&(_, count)| count > 0)
+    }
+}
+
+impl fmt::Display for Chips {
+    /// Formats the chips as stacks from highest to lowest denomination.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::chips::Chips;
+    ///
+    /// assert_eq!(Chips::breakdown(130).to_string(), "1×100 1×25 1×5");
+    /// assert_eq!(Chips::breakdown(0).to_string(), "");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (denomination, count)) in self.stacks().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}×{}", count, denomination.value())?;
+        }
+        Ok(())
+    }
+}
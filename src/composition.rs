@@ -0,0 +1,198 @@
+//! Tracking which ranks remain unseen in a shoe.
+
+use crate::cards::{Rank, ALL_RANKS};
+use crate::hand::BlackjackHand;
+use crate::value::BlackjackValue;
+
+/// The number of cards of each rank that have not been seen yet.
+///
+/// A composition starts out as some number of full decks, and cards are
+/// removed from it as they are seen. It only tracks ranks, since suits don't
+/// matter to the odds of the next card.
+///
+/// A card burned face down hasn't been seen, so it should not be removed. It
+/// is as likely to be any unseen card as the next card is, so leaving it in
+/// gives the same odds for the next card as accounting for every rank it
+/// could have been.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Composition {
+    counts: [u32; 13],
+    len: u32,
+}
+
+impl Composition {
+    /// Returns the composition of `deck_count` full decks.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::composition::Composition;
+    ///
+    /// let composition = Composition::new(6);
+    /// assert_eq!(composition.len(), 312);
+    /// assert_eq!(composition.count(Ace), 24);
+    /// ```
+    pub fn new(deck_count: u32) -> Composition {
+        Composition {
+            counts: [deck_count * 4; 13],
+            len: deck_count * 52,
+        }
+    }
+
+    /// Returns the number of unseen cards.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if every card has been seen.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of unseen cards of the given rank.
+    pub fn count(&self, rank: Rank) -> u32 {
+        self.counts[rank_index(rank)]
+    }
+
+    /// Records that a card of the given rank has been seen.
+    ///
+    /// Returns `false`, leaving the composition unchanged, if there are no
+    /// unseen cards of that rank left.
+    pub fn remove(&mut self, rank: Rank) -> bool {
+        let count = &mut self.counts[rank_index(rank)];
+        if *count == 0 {
+            return false;
+        }
+        *count -= 1;
+        self.len -= 1;
+        true
+    }
+
+    /// Returns the probability that the next card has the given rank.
+    ///
+    /// Returns `0.0` if the composition is empty.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::composition::Composition;
+    ///
+    /// let mut composition = Composition::new(1);
+    /// assert_eq!(composition.probability(Ace), 4.0 / 52.0);
+    ///
+    /// composition.remove(Ace);
+    /// composition.remove(King);
+    /// assert_eq!(composition.probability(Ace), 3.0 / 50.0);
+    /// ```
+    pub fn probability(&self, rank: Rank) -> f64 {
+        self.probability_of(self.count(rank))
+    }
+
+    /// Returns the probability that the next card is a ten or face card.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::composition::Composition;
+    ///
+    /// let mut composition = Composition::new(1);
+    /// assert_eq!(composition.probability_ten_value(), 16.0 / 52.0);
+    ///
+    /// composition.remove(Queen);
+    /// assert_eq!(composition.probability_ten_value(), 15.0 / 51.0);
+    /// ```
+    pub fn probability_ten_value(&self) -> f64 {
+        let count = ALL_RANKS
+            .iter()
+            .filter(|rank| rank.is_ten_value())
+            .map(|&rank| self.count(rank))
+            .sum();
+        self.probability_of(count)
+    }
+
+    /// Returns the probability that drawing one more card would bust `hand`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::composition::Composition;
+    /// use blackjack::hand::BlackjackHand;
+    ///
+    /// let hand: BlackjackHand = "T♠ 6♥".parse().unwrap();
+    /// let mut composition = Composition::new(1);
+    /// composition.remove(Ten);
+    /// composition.remove(Six);
+    ///
+    /// // Anything from a six up busts a hard 16.
+    /// assert_eq!(composition.bust_probability(&hand), 30.0 / 50.0);
+    ///
+    /// let hand: BlackjackHand = "A♠ 6♥".parse().unwrap();
+    /// assert_eq!(composition.bust_probability(&hand), 0.0);
+    /// ```
+    pub fn bust_probability(&self, hand: &BlackjackHand) -> f64 {
+        let hard_total = hand.hard_total();
+        let count = ALL_RANKS
+            .iter()
+            .filter(|rank| hard_total + rank.blackjack_value() > 21)
+            .map(|&rank| self.count(rank))
+            .sum();
+        self.probability_of(count)
+    }
+
+    pub(crate) fn probability_of(&self, count: u32) -> f64 {
+        if self.len == 0 {
+            0.0
+        } else {
+            f64::from(count) / f64::from(self.len)
+        }
+    }
+}
+
+fn rank_index(rank: Rank) -> usize {
+    rank as usize - 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Rank::*;
+
+    #[test]
+    fn hidden_burn_leaves_next_card_odds_unchanged() {
+        let mut composition = Composition::new(1);
+        for &rank in [Ace, Ten, Ten, Five, King].iter() {
+            composition.remove(rank);
+        }
+        for &next in ALL_RANKS.iter() {
+            let after_burn: f64 = ALL_RANKS
+                .iter()
+                .filter(|&&burned| composition.count(burned) > 0)
+                .map(|&burned| {
+                    let mut burned_composition = composition.clone();
+                    burned_composition.remove(burned);
+                    composition.probability(burned) * burned_composition.probability(next)
+                })
+                .sum();
+            assert!((after_burn - composition.probability(next)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn remove_until_empty() {
+        let mut composition = Composition::new(1);
+        for &rank in ALL_RANKS.iter() {
+            for _ in 0..4 {
+                assert!(composition.remove(rank));
+            }
+            assert!(!composition.remove(rank));
+            assert_eq!(composition.probability(rank), 0.0);
+        }
+        assert!(composition.is_empty());
+        assert_eq!(composition.probability(Ace), 0.0);
+        assert_eq!(composition.probability_ten_value(), 0.0);
+    }
+}
@@ -0,0 +1,587 @@
+//! Card counting systems.
+
+use core::fmt;
+
+use crate::cards::{Card, Rank, Suit};
+use crate::hand::BlackjackHand;
+use crate::shoe::Shoe;
+
+/// A card counting system that adds a tag to the running count for each card
+/// seen.
+pub trait CountingSystem {
+    /// Returns the name of the system.
+    fn name(&self) -> &'static str;
+
+    /// Returns the amount the running count changes by when `card` is seen.
+    fn tag(&self, card: Card) -> i32;
+
+    /// Returns the running count to start from after a shuffle of
+    /// `deck_count` decks.
+    ///
+    /// Balanced systems start from zero. Unbalanced systems start from a
+    /// count chosen so that their key counts are the same for any number of
+    /// decks.
+    fn initial_running_count(&self, _deck_count: u32) -> i32 {
+        0
+    }
+
+    /// Returns the running count after seeing `cards`, starting from zero.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Deck;
+    /// use blackjack::counting::{CountingSystem, HiLo};
+    ///
+    /// let mut deck = Deck::default();
+    /// let hands = deck.deal_hands(2, 2);
+    /// assert_eq!(HiLo.running_count(hands[0].iter().chain(hands[1].iter()).cloned()), -4);
+    /// ```
+    fn running_count<I>(&self, cards: I) -> i32
+    where
+        I: IntoIterator<Item = Card>,
+        Self: Sized,
+    {
+        cards.into_iter().map(|card| self.tag(card)).sum()
+    }
+
+    /// Checks a maintained running count against the exact count of the cards
+    /// dealt from `shoe` since it was shuffled, including the initial running
+    /// count.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::counting::{CountingSystem, HiLo};
+    /// use blackjack::shoe::Shoe;
+    ///
+    /// let mut shoe = Shoe::new(1);
+    /// shoe.pop();
+    /// shoe.pop();
+    /// assert!(HiLo.verify(&shoe, -2).is_ok());
+    ///
+    /// let err = HiLo.verify(&shoe, 0).unwrap_err();
+    /// assert_eq!(err.expected(), -2);
+    /// assert_eq!(err.to_string(), "running count is 0, expected -2");
+    /// ```
+    fn verify(&self, shoe: &Shoe, count: i32) -> Result<(), CountMismatch> {
+        let expected = self.initial_running_count(shoe.deck_count() as u32)
+            + shoe.dealt().iter().map(|&card| self.tag(card)).sum::<i32>();
+        if count == expected {
+            Ok(())
+        } else {
+            Err(CountMismatch { count, expected })
+        }
+    }
+}
+
+/// The result of a failed `CountingSystem::verify`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CountMismatch {
+    count: i32,
+    expected: i32,
+}
+
+impl CountMismatch {
+    /// Returns the running count that was checked.
+    pub fn count(&self) -> i32 {
+        self.count
+    }
+
+    /// Returns the exact running count.
+    pub fn expected(&self) -> i32 {
+        self.expected
+    }
+}
+
+impl fmt::Display for CountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "running count is {}, expected {}",
+            self.count, self.expected
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CountMismatch {}
+
+/// The Hi-Lo count: +1 for two through six, -1 for tens and aces.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct HiLo;
+
+impl CountingSystem for HiLo {
+    fn name(&self) -> &'static str {
+        "Hi-Lo"
+    }
+
+    fn tag(&self, card: Card) -> i32 {
+        match card.rank() {
+            Rank::Two | Rank::Three | Rank::Four | Rank::Five | Rank::Six => 1,
+            Rank::Seven | Rank::Eight | Rank::Nine => 0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King | Rank::Ace => -1,
+        }
+    }
+}
+
+/// The Ace-Five count: +1 for fives and -1 for aces, ignoring every other
+/// card.
+///
+/// It tracks the two cards that matter most to the player's edge, so it is
+/// easy to keep but captures much less of the edge than a full count.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct AceFive;
+
+impl CountingSystem for AceFive {
+    fn name(&self) -> &'static str {
+        "Ace-Five"
+    }
+
+    fn tag(&self, card: Card) -> i32 {
+        match card.rank() {
+            Rank::Five => 1,
+            Rank::Ace => -1,
+            _ => 0,
+        }
+    }
+}
+
+/// The Ace/Ten front count: +1 for three through six, -1 for tens and aces,
+/// ignoring twos, sevens, eights, and nines.
+///
+/// It leaves out the cards that matter least, so it is quicker to keep than
+/// Hi-Lo. It is unbalanced and starts each shoe at 4 × decks - 4, so that
+/// the running count falls to -4 when the whole shoe has been seen.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct AceTen;
+
+impl CountingSystem for AceTen {
+    fn name(&self) -> &'static str {
+        "Ace/Ten"
+    }
+
+    fn tag(&self, card: Card) -> i32 {
+        match card.rank() {
+            Rank::Three | Rank::Four | Rank::Five | Rank::Six => 1,
+            Rank::Two | Rank::Seven | Rank::Eight | Rank::Nine => 0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King | Rank::Ace => -1,
+        }
+    }
+
+    fn initial_running_count(&self, deck_count: u32) -> i32 {
+        4 * deck_count as i32 - 4
+    }
+}
+
+/// The Speed Count, which counts whole hands instead of tagging each card.
+///
+/// At the end of a round, each hand still standing, the dealer's included,
+/// adds the number of twos through sixes it holds and subtracts `per_hand`.
+/// Busted hands are picked up before the count is taken, so they are left
+/// out. Because it needs finished hands rather than single cards, it isn't a
+/// `CountingSystem`.
+///
+/// Examples:
+///
+/// ```
+/// use blackjack::counting::SpeedCount;
+/// use blackjack::hand::BlackjackHand;
+///
+/// let hands: Vec<BlackjackHand> = ["T7", "2345", "T6K", "T9"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// assert_eq!(SpeedCount::default().hand_count(&hands[1]), 2);
+/// assert_eq!(SpeedCount::default().round_count(&hands), -2 + 2 - 2);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SpeedCount {
+    /// The amount subtracted for each hand.
+    pub per_hand: i32,
+}
+
+impl Default for SpeedCount {
+    /// Returns the usual Speed Count, which subtracts 2 for each hand.
+    fn default() -> SpeedCount {
+        SpeedCount { per_hand: 2 }
+    }
+}
+
+impl SpeedCount {
+    /// Returns the change in the count for one finished hand, or 0 if it
+    /// busted.
+    pub fn hand_count(&self, hand: &BlackjackHand) -> i32 {
+        if hand.is_bust() {
+            return 0;
+        }
+        let small_cards = hand
+            .cards()
+            .iter()
+            .filter(|card| card.rank() <= Rank::Six)
+            .count();
+        small_cards as i32 - self.per_hand
+    }
+
+    /// Returns the change in the count for every finished hand of a round.
+    pub fn round_count<'a, I>(&self, hands: I) -> i32
+    where
+        I: IntoIterator<Item = &'a BlackjackHand>,
+    {
+        hands.into_iter().map(|hand| self.hand_count(hand)).sum()
+    }
+}
+
+/// The Knock-Out (KO) count: like Hi-Lo, but sevens are +1.
+///
+/// KO is unbalanced, so it starts each shoe at 4 - 4 × decks and is used for
+/// betting with a `KeyCount` rather than a true count.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct KnockOut;
+
+impl CountingSystem for KnockOut {
+    fn name(&self) -> &'static str {
+        "KO"
+    }
+
+    fn tag(&self, card: Card) -> i32 {
+        match card.rank() {
+            Rank::Two | Rank::Three | Rank::Four | Rank::Five | Rank::Six | Rank::Seven => 1,
+            Rank::Eight | Rank::Nine => 0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King | Rank::Ace => -1,
+        }
+    }
+
+    fn initial_running_count(&self, deck_count: u32) -> i32 {
+        4 - 4 * deck_count as i32
+    }
+}
+
+/// The Red Seven count: like Hi-Lo, but red sevens are +1.
+///
+/// Red Seven is unbalanced and starts each shoe at -2 × decks.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct RedSeven;
+
+impl CountingSystem for RedSeven {
+    fn name(&self) -> &'static str {
+        "Red Seven"
+    }
+
+    fn tag(&self, card: Card) -> i32 {
+        match card.rank() {
+            Rank::Seven => match card.suit() {
+                Suit::Diamonds | Suit::Hearts => 1,
+                Suit::Clubs | Suit::Spades => 0,
+            },
+            _ => HiLo.tag(card),
+        }
+    }
+
+    fn initial_running_count(&self, deck_count: u32) -> i32 {
+        -2 * deck_count as i32
+    }
+}
+
+/// A betting rule for unbalanced counts: bet the minimum until the running
+/// count reaches the key count, then bet the maximum.
+///
+/// Examples:
+///
+/// ```
+/// use blackjack::counting::{CountingSystem, KeyCount, KnockOut};
+///
+/// let key_count = KeyCount(-4);
+/// let running_count = KnockOut.initial_running_count(6);
+/// assert_eq!(running_count, -20);
+/// assert_eq!(key_count.bet(running_count, 10, 80), 10);
+/// assert_eq!(key_count.bet(-4, 10, 80), 80);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct KeyCount(pub i32);
+
+impl KeyCount {
+    /// Returns `true` if the running count has reached the key count.
+    pub fn is_reached(&self, running_count: i32) -> bool {
+        running_count >= self.0
+    }
+
+    /// Returns `max_bet` if the key count has been reached, or else
+    /// `min_bet`.
+    pub fn bet(&self, running_count: i32, min_bet: u32, max_bet: u32) -> u32 {
+        if self.is_reached(running_count) {
+            max_bet
+        } else {
+            min_bet
+        }
+    }
+}
+
+/// The Hi-Opt II count: +1 for twos, threes, sixes, and sevens, +2 for fours
+/// and fives, -2 for tens, and 0 for eights, nines, and aces.
+///
+/// Hi-Opt II is ace-neutral, so it is usually played with an `AceSideCount`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct HiOptII;
+
+impl CountingSystem for HiOptII {
+    fn name(&self) -> &'static str {
+        "Hi-Opt II"
+    }
+
+    fn tag(&self, card: Card) -> i32 {
+        match card.rank() {
+            Rank::Two | Rank::Three | Rank::Six | Rank::Seven => 1,
+            Rank::Four | Rank::Five => 2,
+            Rank::Eight | Rank::Nine | Rank::Ace => 0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => -2,
+        }
+    }
+}
+
+/// A separate count of aces, kept alongside a system that doesn't count
+/// them.
+///
+/// Ace-neutral systems like Hi-Opt II leave aces out of the running count
+/// because they play strategy decisions better that way. Aces still matter
+/// for betting, so the side count adds a weight for each ace more or fewer
+/// than expected in the rest of the shoe.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct AceSideCount {
+    deck_count: u32,
+    ace_weight: i32,
+    aces_seen: u32,
+    cards_seen: u32,
+}
+
+impl AceSideCount {
+    /// Returns a side count for a shoe of `deck_count` decks.
+    ///
+    /// `ace_weight` is how much each surplus ace adds to the betting count.
+    /// It is the tag an ace would have in the main system if that system
+    /// counted aces like tens, so 2 for Hi-Opt II and 1 for Hi-Opt I.
+    pub fn new(deck_count: u32, ace_weight: i32) -> AceSideCount {
+        AceSideCount {
+            deck_count,
+            ace_weight,
+            aces_seen: 0,
+            cards_seen: 0,
+        }
+    }
+
+    /// Records that a card has been seen.
+    pub fn see(&mut self, card: Card) {
+        self.cards_seen += 1;
+        if card.rank() == Rank::Ace {
+            self.aces_seen += 1;
+        }
+    }
+
+    /// Returns the number of aces seen.
+    pub fn aces_seen(&self) -> u32 {
+        self.aces_seen
+    }
+
+    /// Returns the number of cards seen.
+    pub fn cards_seen(&self) -> u32 {
+        self.cards_seen
+    }
+
+    /// Returns how many more aces remain than a normal share of the unseen
+    /// cards would hold. Negative values mean the shoe is short of aces.
+    pub fn ace_surplus(&self) -> f64 {
+        let aces_remaining = f64::from(self.deck_count * 4) - f64::from(self.aces_seen);
+        let cards_remaining = f64::from(self.deck_count * 52) - f64::from(self.cards_seen);
+        aces_remaining - cards_remaining / 13.0
+    }
+
+    /// Returns the main system's running count adjusted for the surplus or
+    /// shortage of aces, for use in betting.
+    ///
+    /// Playing decisions should still use the unadjusted running count.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::counting::{AceSideCount, CountingSystem, HiOptII};
+    ///
+    /// let mut side_count = AceSideCount::new(1, 2);
+    /// let mut running_count = 0;
+    /// // Thirteen cards with no aces leave one surplus ace in the deck.
+    /// for &rank in [Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Two].iter() {
+    ///     let card = card(rank, Clubs);
+    ///     running_count += HiOptII.tag(card);
+    ///     side_count.see(card);
+    /// }
+    /// assert_eq!(side_count.ace_surplus(), 1.0);
+    /// assert_eq!(side_count.betting_count(running_count), f64::from(running_count) + 2.0);
+    /// ```
+    pub fn betting_count(&self, running_count: i32) -> f64 {
+        f64::from(running_count) + f64::from(self.ace_weight) * self.ace_surplus()
+    }
+}
+
+/// How finely the number of decks remaining is estimated for a true count.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DeckResolution {
+    /// Whole decks.
+    #[default]
+    Full,
+    /// Half decks.
+    Half,
+    /// Quarter decks.
+    Quarter,
+}
+
+impl DeckResolution {
+    fn steps_per_deck(self) -> i32 {
+        match self {
+            DeckResolution::Full => 1,
+            DeckResolution::Half => 2,
+            DeckResolution::Quarter => 4,
+        }
+    }
+}
+
+/// How a fractional true count is made a whole number.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Rounding {
+    /// Toward negative infinity, so -1.5 becomes -2.
+    #[default]
+    Floor,
+    /// Toward zero, so -1.5 becomes -1.
+    Truncate,
+    /// To the nearest whole number, with halves away from zero.
+    Nearest,
+}
+
+/// The way a running count is converted to a true count.
+///
+/// Different books teach different conversions, and they give measurably
+/// different results, so both the deck estimate and the rounding can be
+/// chosen.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TrueCount {
+    pub resolution: DeckResolution,
+    pub rounding: Rounding,
+}
+
+impl TrueCount {
+    /// Returns the true count for a running count with `cards_remaining`
+    /// cards left to deal.
+    ///
+    /// The decks remaining are estimated to the nearest step of the
+    /// resolution, and never less than one step.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::counting::{DeckResolution, Rounding, TrueCount};
+    ///
+    /// // 130 cards is two and a half decks.
+    /// let full = TrueCount { resolution: DeckResolution::Full, rounding: Rounding::Floor };
+    /// assert_eq!(full.convert(7, 130), 2);
+    ///
+    /// let half = TrueCount { resolution: DeckResolution::Half, rounding: Rounding::Floor };
+    /// assert_eq!(half.convert(7, 130), 2);
+    /// assert_eq!(half.convert(-7, 130), -3);
+    ///
+    /// let half = TrueCount { resolution: DeckResolution::Half, rounding: Rounding::Nearest };
+    /// assert_eq!(half.convert(7, 130), 3);
+    /// ```
+    pub fn convert(&self, running_count: i32, cards_remaining: usize) -> i32 {
+        let steps_per_deck = self.resolution.steps_per_deck();
+        let cards_per_step = 52 / steps_per_deck as usize;
+        let steps = ((cards_remaining + cards_per_step / 2) / cards_per_step).max(1) as i32;
+        let numerator = running_count * steps_per_deck;
+        match self.rounding {
+            Rounding::Floor => numerator.div_euclid(steps),
+            Rounding::Truncate => numerator / steps,
+            Rounding::Nearest => (2 * numerator + numerator.signum() * steps) / (2 * steps),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Deck;
+
+    #[test]
+    fn balanced_systems_sum_to_zero() {
+        let deck = Deck::default();
+        assert_eq!(HiLo.running_count(deck.iter().cloned()), 0);
+        assert_eq!(AceFive.running_count(deck.iter().cloned()), 0);
+        assert_eq!(HiOptII.running_count(deck.iter().cloned()), 0);
+    }
+
+    #[test]
+    fn unbalanced_systems_end_at_fixed_counts() {
+        let deck = Deck::default();
+        let ko = KnockOut.initial_running_count(1) + KnockOut.running_count(deck.iter().cloned());
+        assert_eq!(ko, 4);
+        let red_seven =
+            RedSeven.initial_running_count(1) + RedSeven.running_count(deck.iter().cloned());
+        assert_eq!(red_seven, 0);
+        let per_deck = AceTen.running_count(deck.iter().cloned());
+        for deck_count in 1..=8 {
+            let ace_ten = AceTen.initial_running_count(deck_count) + deck_count as i32 * per_deck;
+            assert_eq!(ace_ten, -4);
+        }
+    }
+
+    #[test]
+    fn speed_count_skips_busted_hands() {
+        use crate::rules::tests::hand;
+
+        let speed = SpeedCount::default();
+        assert_eq!(speed.hand_count(&hand("23")), 0);
+        assert_eq!(speed.hand_count(&hand("A9")), -2);
+        assert_eq!(speed.hand_count(&hand("66T")), 0);
+        assert_eq!(speed.hand_count(&hand("2345")), 2);
+        assert_eq!(SpeedCount { per_hand: 1 }.hand_count(&hand("T9")), -1);
+    }
+
+    #[test]
+    fn ace_ten_ignores_middle_cards() {
+        use crate::cards::card;
+        use crate::cards::Rank::*;
+
+        let ranks = [Two, Three, Six, Seven, Nine, Ten, King, Ace];
+        let tags = ranks
+            .iter()
+            .map(|&rank| AceTen.tag(card(rank, Suit::Clubs)));
+        assert!(tags.eq([0, 1, 1, 0, 0, -1, -1, -1].iter().cloned()));
+    }
+
+    #[test]
+    fn true_count_rounding() {
+        let convert = |rounding, running_count| {
+            TrueCount {
+                resolution: DeckResolution::Full,
+                rounding,
+            }
+            .convert(running_count, 104)
+        };
+        assert_eq!(convert(Rounding::Floor, -3), -2);
+        assert_eq!(convert(Rounding::Truncate, -3), -1);
+        assert_eq!(convert(Rounding::Nearest, -3), -2);
+        assert_eq!(convert(Rounding::Floor, 3), 1);
+        assert_eq!(convert(Rounding::Truncate, 3), 1);
+        assert_eq!(convert(Rounding::Nearest, 3), 2);
+        assert_eq!(convert(Rounding::Nearest, 0), 0);
+    }
+
+    #[test]
+    fn true_count_never_divides_by_less_than_one_step() {
+        let quarter = TrueCount {
+            resolution: DeckResolution::Quarter,
+            rounding: Rounding::Truncate,
+        };
+        assert_eq!(quarter.convert(3, 5), 12);
+        assert_eq!(quarter.convert(3, 0), 12);
+    }
+}
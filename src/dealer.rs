@@ -0,0 +1,232 @@
+//! Probabilities of the dealer's final hand.
+
+use crate::cards::{Rank, ALL_RANKS};
+use crate::composition::Composition;
+use crate::value::{BlackjackValue, SOFT_ACE_BONUS};
+
+/// The probability of each way the dealer's hand can finish.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct DealerOutcomes {
+    totals: [f64; 5],
+    blackjack: f64,
+    bust: f64,
+    bust_22: f64,
+    bust_cards: [f64; 6],
+}
+
+impl DealerOutcomes {
+    /// Returns the probability that the dealer stands on `total`, for totals
+    /// from 17 to 21. A two-card 21 is counted by `blackjack()` instead.
+    ///
+    /// Returns `0.0` for any other total, since the dealer never stands on one.
+    pub fn total(&self, total: u32) -> f64 {
+        match total {
+            17..=21 => self.totals[total as usize - 17],
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the probability that the dealer has a blackjack.
+    pub fn blackjack(&self) -> f64 {
+        self.blackjack
+    }
+
+    /// Returns the probability that the dealer busts.
+    pub fn bust(&self) -> f64 {
+        self.bust
+    }
+
+    /// Returns the probability that the dealer busts with exactly `count`
+    /// cards, for counts from 3 to 8. A count of 8 includes every bust of 8
+    /// cards or more.
+    ///
+    /// Returns `0.0` for any other count, since the dealer can't bust with
+    /// fewer than three cards.
+    pub fn bust_with_cards(&self, count: usize) -> f64 {
+        match count {
+            3..=8 => self.bust_cards[count - 3],
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the probability that the dealer busts with exactly 22, which
+    /// is a push under the push-22 rule. It is included in `bust()`.
+    pub fn bust_22(&self) -> f64 {
+        self.bust_22
+    }
+}
+
+/// Returns the probabilities of the dealer's final hand, given the upcard and
+/// the composition of the cards the dealer will draw from.
+///
+/// The upcard should already have been removed from `composition`. The dealer
+/// hits until reaching 17, and also hits a soft 17 if `hits_soft_17` is set.
+///
+/// The probabilities are not conditioned on the dealer having checked for
+/// blackjack, so when the upcard is an ace or a ten they include the dealer's
+/// blackjacks.
+///
+/// Examples:
+///
+/// ```
+/// use blackjack::cards::Rank::*;
+/// use blackjack::composition::Composition;
+/// use blackjack::dealer::dealer_outcomes;
+///
+/// let mut composition = Composition::new(6);
+/// composition.remove(Six);
+/// let outcomes = dealer_outcomes(Six, &composition, false);
+/// assert!(outcomes.bust() > 0.41 && outcomes.bust() < 0.43);
+/// assert_eq!(outcomes.blackjack(), 0.0);
+/// ```
+pub fn dealer_outcomes(
+    upcard: Rank,
+    composition: &Composition,
+    hits_soft_17: bool,
+) -> DealerOutcomes {
+    let mut outcomes = DealerOutcomes::default();
+    let dealer = DealerHand {
+        hard_total: upcard.blackjack_value(),
+        has_ace: upcard.is_ace(),
+        len: 1,
+    };
+    play(dealer, composition, 1.0, hits_soft_17, &mut outcomes);
+    outcomes
+}
+
+#[derive(Clone, Copy)]
+struct DealerHand {
+    hard_total: u32,
+    has_ace: bool,
+    len: usize,
+}
+
+impl DealerHand {
+    fn push(self, rank: Rank) -> DealerHand {
+        DealerHand {
+            hard_total: self.hard_total + rank.blackjack_value(),
+            has_ace: self.has_ace || rank.is_ace(),
+            len: self.len + 1,
+        }
+    }
+}
+
+/// Adds the outcomes of the dealer playing out `dealer`, which has been reached
+/// with probability `probability`, to `outcomes`.
+fn play(
+    dealer: DealerHand,
+    composition: &Composition,
+    probability: f64,
+    hits_soft_17: bool,
+    outcomes: &mut DealerOutcomes,
+) {
+    let soft = dealer.has_ace && dealer.hard_total + SOFT_ACE_BONUS <= 21;
+    let total = if soft {
+        dealer.hard_total + SOFT_ACE_BONUS
+    } else {
+        dealer.hard_total
+    };
+
+    if total > 21 {
+        outcomes.bust += probability;
+        if total == 22 {
+            outcomes.bust_22 += probability;
+        }
+        outcomes.bust_cards[dealer.len.min(8) - 3] += probability;
+        return;
+    }
+    if total >= 17 && !(total == 17 && soft && hits_soft_17) {
+        if total == 21 && dealer.len == 2 {
+            outcomes.blackjack += probability;
+        } else {
+            outcomes.totals[total as usize - 17] += probability;
+        }
+        return;
+    }
+
+    // All ten-value cards lead to the same outcomes, so they are drawn as one
+    // group, represented by whichever ten-value rank is still available.
+    let ten_count: u32 = ALL_RANKS
+        .iter()
+        .filter(|rank| rank.is_ten_value())
+        .map(|&rank| composition.count(rank))
+        .sum();
+    let ten = ALL_RANKS
+        .iter()
+        .cloned()
+        .find(|&rank| rank.is_ten_value() && composition.count(rank) > 0);
+    let draws = ALL_RANKS
+        .iter()
+        .filter(|rank| !rank.is_ten_value())
+        .map(|&rank| (rank, composition.count(rank)))
+        .chain(ten.map(|rank| (rank, ten_count)));
+
+    for (rank, count) in draws {
+        if count == 0 {
+            continue;
+        }
+        let mut remaining = composition.clone();
+        let draw_probability = probability * composition.probability_of(count);
+        remaining.remove(rank);
+        play(
+            dealer.push(rank),
+            &remaining,
+            draw_probability,
+            hits_soft_17,
+            outcomes,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Rank::*;
+
+    fn sum(outcomes: &DealerOutcomes) -> f64 {
+        (17..=21).map(|total| outcomes.total(total)).sum::<f64>()
+            + outcomes.blackjack()
+            + outcomes.bust()
+    }
+
+    #[test]
+    fn outcomes_add_up() {
+        for &upcard in ALL_RANKS.iter() {
+            let mut composition = Composition::new(2);
+            composition.remove(upcard);
+            for &hits_soft_17 in [false, true].iter() {
+                let outcomes = dealer_outcomes(upcard, &composition, hits_soft_17);
+                assert!((sum(&outcomes) - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn blackjack_only_with_ace_or_ten() {
+        let composition = Composition::new(1);
+        assert_eq!(dealer_outcomes(Nine, &composition, false).blackjack(), 0.0);
+        assert!(dealer_outcomes(Ace, &composition, false).blackjack() > 0.3);
+        assert!(dealer_outcomes(King, &composition, false).blackjack() > 0.07);
+    }
+
+    #[test]
+    fn hitting_soft_17() {
+        let mut composition = Composition::new(6);
+        composition.remove(Six);
+        let stands = dealer_outcomes(Six, &composition, false);
+        let hits = dealer_outcomes(Six, &composition, true);
+        assert!(hits.bust() > stands.bust());
+        assert!(hits.total(17) < stands.total(17));
+    }
+
+    #[test]
+    fn bust_breakdowns_add_up() {
+        let mut composition = Composition::new(6);
+        composition.remove(Six);
+        let outcomes = dealer_outcomes(Six, &composition, false);
+        assert!(outcomes.bust_22() > 0.0);
+        assert!(outcomes.bust_22() < outcomes.bust());
+        let by_cards: f64 = (3..=8).map(|count| outcomes.bust_with_cards(count)).sum();
+        assert!((by_cards - outcomes.bust()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,518 @@
+//! A player's or dealer's hand, valued by the rules of Blackjack.
+
+use core::fmt;
+use core::ops::Index;
+use core::str::FromStr;
+
+use crate::cards::{card, Card, Hand, Rank, Suit, ALL_SUITS};
+use crate::value::{BlackjackValue, SOFT_ACE_BONUS};
+
+/// A `Hand` together with the Blackjack valuation of its cards.
+///
+/// The valuation is kept up to date as cards are added, so totals take
+/// constant time however many cards the hand holds.
+///
+/// A hand also records whether it was made by splitting a pair, since a
+/// two-card 21 after a split is not a blackjack.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct BlackjackHand {
+    hand: Hand,
+    hard_total: u32,
+    has_ace: bool,
+    split: bool,
+}
+
+impl From<Hand> for BlackjackHand {
+    fn from(hand: Hand) -> Self {
+        BlackjackHand {
+            hard_total: hand.iter().map(|card| card.blackjack_value()).sum(),
+            has_ace: hand.iter().any(|card| card.is_ace()),
+            hand,
+            split: false,
+        }
+    }
+}
+
+impl Index<usize> for BlackjackHand {
+    type Output = Card;
+
+    fn index(&self, index: usize) -> &Card {
+        &self.hand[index]
+    }
+}
+
+impl BlackjackHand {
+    /// Returns a hand made by splitting a pair, holding one card of the pair.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::hand::BlackjackHand;
+    ///
+    /// let mut hand = BlackjackHand::split_from(card(Ace, Spades));
+    /// hand.push(card(King, Hearts));
+    /// assert_eq!(hand.total(), 21);
+    /// assert!(hand.is_split());
+    /// assert!(!hand.is_blackjack());
+    /// ```
+    pub fn split_from(card: Card) -> BlackjackHand {
+        let mut hand = BlackjackHand {
+            split: true,
+            ..BlackjackHand::default()
+        };
+        hand.push(card);
+        hand
+    }
+
+    /// Returns `true` if the hand was made by splitting a pair.
+    pub fn is_split(&self) -> bool {
+        self.split
+    }
+
+    /// Returns the underlying cards.
+    pub fn cards(&self) -> &Hand {
+        &self.hand
+    }
+
+    /// Returns the count of cards in the hand.
+    pub fn len(&self) -> usize {
+        self.hand.len()
+    }
+
+    /// Returns `true` if the hand contains no cards.
+    pub fn is_empty(&self) -> bool {
+        self.hand.is_empty()
+    }
+
+    /// Adds a card to the hand.
+    pub fn push(&mut self, card: Card) {
+        self.hand.push(card);
+        self.hard_total += card.blackjack_value();
+        self.has_ace |= card.is_ace();
+    }
+
+    /// Returns the total with every ace counted as 1.
+    pub fn hard_total(&self) -> u32 {
+        self.hard_total
+    }
+
+    /// Returns the best total for the hand.
+    ///
+    /// One ace is counted as 11 if that doesn't take the total over 21.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::hand::BlackjackHand;
+    ///
+    /// let mut hand = BlackjackHand::default();
+    /// hand.push(card(Ace, Spades));
+    /// hand.push(card(Six, Hearts));
+    /// assert_eq!(hand.total(), 17);
+    /// assert!(hand.is_soft());
+    ///
+    /// hand.push(card(Nine, Clubs));
+    /// assert_eq!(hand.total(), 16);
+    /// assert!(!hand.is_soft());
+    /// ```
+    pub fn total(&self) -> u32 {
+        let hard_total = self.hard_total();
+        if self.has_ace() && hard_total + SOFT_ACE_BONUS <= 21 {
+            hard_total + SOFT_ACE_BONUS
+        } else {
+            hard_total
+        }
+    }
+
+    /// Returns `true` if the hand's total counts an ace as 11.
+    pub fn is_soft(&self) -> bool {
+        self.has_ace() && self.hard_total() + SOFT_ACE_BONUS <= 21
+    }
+
+    /// Returns `true` if the hand is two cards of the same Blackjack value.
+    ///
+    /// Any two ten-value cards are a pair, so a king and a queen is a pair of
+    /// tens.
+    pub fn is_pair(&self) -> bool {
+        self.len() == 2 && self[0].blackjack_value() == self[1].blackjack_value()
+    }
+
+    /// Returns `true` if the hand is an ace and a ten-value card, and wasn't
+    /// made by splitting.
+    pub fn is_blackjack(&self) -> bool {
+        !self.split && self.len() == 2 && self.total() == 21
+    }
+
+    /// Returns `true` if the hand's total is over 21.
+    pub fn is_bust(&self) -> bool {
+        self.hard_total() > 21
+    }
+
+    /// Returns the canonical key used to look the hand up in strategy tables.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::hand::{BlackjackHand, StrategyKey};
+    ///
+    /// let mut hand = BlackjackHand::default();
+    /// hand.push(card(Eight, Spades));
+    /// hand.push(card(Eight, Hearts));
+    /// assert_eq!(hand.strategy_key(), StrategyKey::Pair(8));
+    ///
+    /// hand.push(card(Ace, Clubs));
+    /// assert_eq!(hand.strategy_key(), StrategyKey::Hard(17));
+    ///
+    /// let mut hand = BlackjackHand::default();
+    /// hand.push(card(Ace, Spades));
+    /// hand.push(card(Seven, Diamonds));
+    /// assert_eq!(hand.strategy_key(), StrategyKey::Soft(18));
+    /// ```
+    pub fn strategy_key(&self) -> StrategyKey {
+        if self.is_pair() {
+            StrategyKey::Pair(self[0].blackjack_value())
+        } else if self.is_soft() {
+            StrategyKey::Soft(self.total())
+        } else {
+            StrategyKey::Hard(self.total())
+        }
+    }
+
+    fn has_ace(&self) -> bool {
+        self.has_ace
+    }
+}
+
+/// The rows of a basic strategy chart.
+///
+/// A hand is keyed as a pair if it is exactly two cards of the same value,
+/// otherwise as a soft or hard total.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum StrategyKey {
+    /// Two cards of the given Blackjack value (1 for aces).
+    Pair(u32),
+    /// A total that counts an ace as 11.
+    Soft(u32),
+    /// A total with no ace counted as 11.
+    Hard(u32),
+}
+
+impl fmt::Display for StrategyKey {
+    /// Formats the key the way strategy charts label their rows.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::hand::StrategyKey;
+    ///
+    /// assert_eq!(StrategyKey::Pair(1).to_string(), "A,A");
+    /// assert_eq!(StrategyKey::Pair(8).to_string(), "8,8");
+    /// assert_eq!(StrategyKey::Pair(10).to_string(), "T,T");
+    /// assert_eq!(StrategyKey::Soft(18).to_string(), "soft 18");
+    /// assert_eq!(StrategyKey::Hard(16).to_string(), "hard 16");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StrategyKey::Pair(1) => write!(f, "A,A"),
+            StrategyKey::Pair(10) => write!(f, "T,T"),
+            StrategyKey::Pair(value) => write!(f, "{},{}", value, value),
+            StrategyKey::Soft(total) => write!(f, "soft {}", total),
+            StrategyKey::Hard(total) => write!(f, "hard {}", total),
+        }
+    }
+}
+
+impl FromStr for BlackjackHand {
+    type Err = ParseHandError;
+
+    /// Parses a hand from card notation such as "A7", "8,8", or "K♠ 6♥".
+    ///
+    /// Cards may be separated by commas or spaces, or written together.
+    /// Suits are optional. Cards written without one are given suits in the
+    /// order clubs, diamonds, hearts, spades, so that "8,8" is two different
+    /// eights.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::hand::{BlackjackHand, StrategyKey};
+    ///
+    /// let hand: BlackjackHand = "A7".parse().unwrap();
+    /// assert_eq!(hand.strategy_key(), StrategyKey::Soft(18));
+    ///
+    /// let hand: BlackjackHand = "10,6,5".parse().unwrap();
+    /// assert_eq!(hand.total(), 21);
+    ///
+    /// let hand: BlackjackHand = "K♠ 6h".parse().unwrap();
+    /// assert_eq!(hand[0], card(King, Spades));
+    /// assert_eq!(hand[1], card(Six, Hearts));
+    /// ```
+    fn from_str(s: &str) -> Result<BlackjackHand, ParseHandError> {
+        let mut hand = BlackjackHand::default();
+        parse_cards(s, &mut 0, &mut hand)?;
+        if hand.is_empty() {
+            Err(ParseHandError::NoCards)
+        } else {
+            Ok(hand)
+        }
+    }
+}
+
+/// A player's hand against a dealer's upcard.
+#[derive(Debug)]
+pub struct Scenario {
+    player: BlackjackHand,
+    upcard: Card,
+}
+
+impl Scenario {
+    /// Returns the player's hand.
+    pub fn player(&self) -> &BlackjackHand {
+        &self.player
+    }
+
+    /// Returns the dealer's upcard.
+    pub fn upcard(&self) -> Card {
+        self.upcard
+    }
+}
+
+impl FromStr for Scenario {
+    type Err = ParseHandError;
+
+    /// Parses a scenario written as a player's hand, "v" or "vs", and the
+    /// dealer's upcard, such as "A7 v T" or "8,8 vs 9".
+    ///
+    /// The hand and upcard use the same notation as parsing a `BlackjackHand`.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::hand::{Scenario, StrategyKey};
+    ///
+    /// let scenario: Scenario = "8,8 vs 9".parse().unwrap();
+    /// assert_eq!(scenario.player().strategy_key(), StrategyKey::Pair(8));
+    /// assert_eq!(scenario.upcard().rank(), Nine);
+    ///
+    /// assert!("A7".parse::<Scenario>().is_err());
+    /// assert!("A7 v T 6".parse::<Scenario>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Scenario, ParseHandError> {
+        let mut tokens = s.split_whitespace();
+        let mut player = BlackjackHand::default();
+        let mut next_suit = 0;
+        for token in tokens.by_ref() {
+            if is_versus(token) {
+                break;
+            }
+            parse_cards(token, &mut next_suit, &mut player)?;
+        }
+        if player.is_empty() {
+            return Err(ParseHandError::NoCards);
+        }
+
+        let mut dealer = BlackjackHand::default();
+        for token in tokens {
+            parse_cards(token, &mut next_suit, &mut dealer)?;
+        }
+        match dealer.len() {
+            0 => Err(ParseHandError::MissingUpcard),
+            1 => Ok(Scenario {
+                player,
+                upcard: dealer[0],
+            }),
+            _ => Err(ParseHandError::TooManyUpcards),
+        }
+    }
+}
+
+fn is_versus(token: &str) -> bool {
+    ["v", "vs", "vs."]
+        .iter()
+        .any(|versus| token.eq_ignore_ascii_case(versus))
+}
+
+/// Parses the cards in `s` and adds them to `hand`.
+///
+/// `next_suit` is the index in `ALL_SUITS` of the suit to give the next card
+/// written without one.
+fn parse_cards(
+    s: &str,
+    next_suit: &mut usize,
+    hand: &mut BlackjackHand,
+) -> Result<(), ParseHandError> {
+    let tokens = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty());
+    for token in tokens {
+        let mut rest = token;
+        while !rest.is_empty() {
+            let (rank, after_rank) = Rank::parse_prefix(rest).ok_or(ParseHandError::InvalidCard)?;
+            let (suit, after_suit) = match Suit::parse_prefix(after_rank) {
+                Some(parsed) => parsed,
+                None => {
+                    let suit = ALL_SUITS[*next_suit % ALL_SUITS.len()];
+                    *next_suit += 1;
+                    (suit, after_rank)
+                }
+            };
+            hand.push(card(rank, suit));
+            rest = after_suit;
+        }
+    }
+    Ok(())
+}
+
+/// The error returned when a `BlackjackHand` or `Scenario` can't be parsed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseHandError {
+    /// Something that isn't a card was found.
+    InvalidCard,
+    /// No player cards were given.
+    NoCards,
+    /// The dealer's upcard is missing.
+    MissingUpcard,
+    /// More than one dealer card was given.
+    TooManyUpcards,
+}
+
+impl fmt::Display for ParseHandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ParseHandError::InvalidCard => "invalid card notation",
+            ParseHandError::NoCards => "no cards in hand",
+            ParseHandError::MissingUpcard => "missing dealer upcard",
+            ParseHandError::TooManyUpcards => "more than one dealer upcard",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseHandError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card;
+    use crate::cards::Rank::*;
+    use crate::cards::Suit::*;
+
+    fn hand(cards: &[Card]) -> BlackjackHand {
+        let mut hand = BlackjackHand::default();
+        for &card in cards {
+            hand.push(card);
+        }
+        hand
+    }
+
+    #[test]
+    fn totals() {
+        assert_eq!(hand(&[card(Ace, Clubs), card(Ace, Hearts)]).total(), 12);
+        assert_eq!(hand(&[card(Ace, Clubs), card(King, Hearts)]).total(), 21);
+        assert!(hand(&[card(Ace, Clubs), card(King, Hearts)]).is_blackjack());
+        assert!(!hand(&[card(Ace, Clubs), card(Five, Hearts), card(Five, Clubs)]).is_blackjack());
+
+        let busted = hand(&[card(Ten, Clubs), card(Six, Hearts), card(King, Spades)]);
+        assert_eq!(busted.total(), 26);
+        assert!(busted.is_bust());
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        let mut original = hand(&[card(Ace, Clubs), card(Five, Hearts)]);
+        let snapshot = original.clone();
+        original.push(card(Nine, Spades));
+        assert_eq!(original.total(), 15);
+        assert_eq!(snapshot.total(), 16);
+        assert_eq!(snapshot.len(), 2);
+    }
+
+    #[test]
+    fn from_hand_matches_pushing() {
+        let cards = [card(Ace, Clubs), card(Five, Hearts), card(Ace, Spades)];
+        let mut cards_hand = Hand::default();
+        for &card in cards.iter() {
+            cards_hand.push(card);
+        }
+        let from = BlackjackHand::from(cards_hand);
+        assert_eq!(from, hand(&cards));
+        assert_eq!(from.hard_total(), 7);
+        assert_eq!(from.total(), 17);
+    }
+
+    #[test]
+    fn strategy_keys() {
+        let key = |cards: &[Card]| hand(cards).strategy_key();
+        assert_eq!(
+            key(&[card(Ace, Clubs), card(Ace, Hearts)]),
+            StrategyKey::Pair(1)
+        );
+        assert_eq!(
+            key(&[card(King, Clubs), card(Queen, Hearts)]),
+            StrategyKey::Pair(10)
+        );
+        assert_eq!(
+            key(&[card(Ten, Clubs), card(Six, Hearts)]),
+            StrategyKey::Hard(16)
+        );
+        assert_eq!(
+            key(&[card(Ace, Clubs), card(Ten, Hearts)]),
+            StrategyKey::Soft(21)
+        );
+        assert_eq!(
+            key(&[card(Ace, Clubs), card(Two, Hearts), card(Three, Spades)]),
+            StrategyKey::Soft(16)
+        );
+        assert_eq!(
+            key(&[card(Ace, Clubs), card(Six, Hearts), card(Nine, Spades)]),
+            StrategyKey::Hard(16)
+        );
+    }
+
+    #[test]
+    fn parse_scenarios() {
+        let scenario: Scenario = "A7 v T".parse().unwrap();
+        assert_eq!(scenario.player()[0], card(Ace, Clubs));
+        assert_eq!(scenario.player()[1], card(Seven, Diamonds));
+        assert_eq!(scenario.upcard(), card(Ten, Hearts));
+
+        let scenario: Scenario = "Q♠ 6♠ VS A♥".parse().unwrap();
+        assert_eq!(scenario.player().total(), 16);
+        assert_eq!(scenario.upcard(), card(Ace, Hearts));
+
+        assert_eq!(
+            "v T".parse::<Scenario>().unwrap_err(),
+            ParseHandError::NoCards
+        );
+        assert_eq!(
+            "A7 vs".parse::<Scenario>().unwrap_err(),
+            ParseHandError::MissingUpcard
+        );
+        assert_eq!(
+            "A7 vs 9 9".parse::<Scenario>().unwrap_err(),
+            ParseHandError::TooManyUpcards
+        );
+        assert_eq!(
+            "A1 vs 9".parse::<Scenario>().unwrap_err(),
+            ParseHandError::InvalidCard
+        );
+        assert_eq!(
+            "".parse::<BlackjackHand>().unwrap_err(),
+            ParseHandError::NoCards
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Long names of cards in several languages.
+//!
+//! Card symbols, as printed by `Display`, are the same in every language.
+
+use alloc::format;
+use alloc::string::String;
+
+use crate::cards::{Card, Rank, Suit};
+
+/// A language that card names can be written in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+    French,
+}
+
+/// Array of all `Language` values.
+pub const ALL_LANGUAGES: [Language; 4] = [
+    Language::English,
+    Language::Spanish,
+    Language::German,
+    Language::French,
+];
+
+impl Language {
+    /// Returns the ISO 639-1 code for the language.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::French => "fr",
+        }
+    }
+
+    /// Returns the language for an ISO 639-1 code, or a locale name that
+    /// starts with one, such as "de_DE.UTF-8".
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::i18n::Language;
+    ///
+    /// assert_eq!(Language::from_code("es"), Some(Language::Spanish));
+    /// assert_eq!(Language::from_code("de_DE.UTF-8"), Some(Language::German));
+    /// assert_eq!(Language::from_code("FR"), Some(Language::French));
+    /// assert_eq!(Language::from_code("ja_JP"), None);
+    /// ```
+    pub fn from_code(code: &str) -> Option<Language> {
+        let language = code.split(['_', '-', '.']).next()?;
+        ALL_LANGUAGES
+            .iter()
+            .cloned()
+            .find(|l| l.code().eq_ignore_ascii_case(language))
+    }
+
+    /// Returns the name of a rank.
+    pub fn rank_name(self, rank: Rank) -> &'static str {
+        let names = match self {
+            Language::English => [
+                "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack",
+                "Queen", "King", "Ace",
+            ],
+            Language::Spanish => [
+                "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "diez", "jota",
+                "reina", "rey", "as",
+            ],
+            Language::German => [
+                "Zwei", "Drei", "Vier", "Fünf", "Sechs", "Sieben", "Acht", "Neun", "Zehn", "Bube",
+                "Dame", "König", "Ass",
+            ],
+            Language::French => [
+                "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix", "valet",
+                "dame", "roi", "as",
+            ],
+        };
+        names[rank as usize - 2]
+    }
+
+    /// Returns the name of a suit.
+    pub fn suit_name(self, suit: Suit) -> &'static str {
+        let names = match self {
+            Language::English => ["Clubs", "Diamonds", "Hearts", "Spades"],
+            Language::Spanish => ["tréboles", "diamantes", "corazones", "picas"],
+            Language::German => ["Kreuz", "Karo", "Herz", "Pik"],
+            Language::French => ["trèfle", "carreau", "cœur", "pique"],
+        };
+        names[suit as usize]
+    }
+
+    /// Returns the long name of a card.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::i18n::Language;
+    ///
+    /// let queen = card(Queen, Hearts);
+    /// assert_eq!(Language::English.card_name(queen), "Queen of Hearts");
+    /// assert_eq!(Language::Spanish.card_name(queen), "reina de corazones");
+    /// assert_eq!(Language::German.card_name(queen), "Herz-Dame");
+    /// assert_eq!(Language::French.card_name(queen), "dame de cœur");
+    /// ```
+    pub fn card_name(self, card: Card) -> String {
+        let rank = self.rank_name(card.rank());
+        let suit = self.suit_name(card.suit());
+        match self {
+            Language::English => format!("{} of {}", rank, suit),
+            Language::Spanish | Language::French => format!("{} de {}", rank, suit),
+            Language::German => format!("{}-{}", suit, rank),
+        }
+    }
+}
@@ -1 +1,26 @@
+//! Types and calculations for playing and analyzing Blackjack.
+//!
+//! The library does no terminal or file I/O, so it can be embedded in servers
+//! and GUIs. The command-line program is the separate `blackjack-cli` crate.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod bankroll;
 pub mod cards;
+pub mod chips;
+pub mod composition;
+pub mod counting;
+pub mod dealer;
+pub mod hand;
+pub mod i18n;
+pub mod rules;
+pub mod settlement;
+pub mod shoe;
+pub mod side_bets;
+pub mod strategy;
+#[cfg(feature = "svg")]
+pub mod svg;
+pub mod value;
@@ -0,0 +1,715 @@
+//! The rules of a Blackjack table.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::cards::{Rank, Suit};
+use crate::hand::BlackjackHand;
+use crate::settlement::Reason;
+
+/// When the player may give up half the bet instead of playing the hand.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Surrender {
+    /// No surrender.
+    #[default]
+    None,
+    /// Surrender after the dealer checks for blackjack.
+    Late,
+    /// Surrender before the dealer checks for blackjack.
+    Early,
+}
+
+/// A payout, as the amount won for a given amount bet: `Payout(3, 2)` pays
+/// 3:2.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Payout(pub u32, pub u32);
+
+impl Payout {
+    /// Returns the amount won on a winning bet of `bet`, rounded down.
+    ///
+    /// The amount is a `u64`, so it can't overflow for any bet and payout.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::rules::Payout;
+    ///
+    /// assert_eq!(Payout(3, 2).winnings(10), 15);
+    /// assert_eq!(Payout(6, 5).winnings(10), 12);
+    /// ```
+    pub fn winnings(self, bet: u32) -> u64 {
+        u64::from(bet) * u64::from(self.0) / u64::from(self.1)
+    }
+}
+
+impl fmt::Display for Payout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.0, self.1)
+    }
+}
+
+/// A special player hand that some tables pay a bonus for.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BonusHand {
+    /// Five or more cards totaling 21.
+    FiveCard21,
+    /// A six, seven, and eight, all of one suit.
+    SuitedSixSevenEight,
+    /// Three sevens.
+    SevenSevenSeven,
+    /// A blackjack with both cards of one suit.
+    SuitedBlackjack,
+    /// A blackjack with both cards of the given suit.
+    BlackjackInSuit(Suit),
+}
+
+impl BonusHand {
+    /// Returns `true` if `hand` qualifies for the bonus.
+    pub fn matches(self, hand: &BlackjackHand) -> bool {
+        let cards = hand.cards();
+        let suited = cards.iter().all(|card| card.suit() == cards[0].suit());
+        match self {
+            BonusHand::FiveCard21 => hand.len() >= 5 && hand.total() == 21,
+            BonusHand::SuitedSixSevenEight => {
+                let mut ranks = [Rank::Two; 3];
+                if hand.len() != 3 || !suited {
+                    return false;
+                }
+                for (i, card) in cards.iter().enumerate() {
+                    ranks[i] = card.rank();
+                }
+                ranks.sort();
+                ranks == [Rank::Six, Rank::Seven, Rank::Eight]
+            }
+            BonusHand::SevenSevenSeven => {
+                hand.len() == 3 && cards.iter().all(|card| card.rank() == Rank::Seven)
+            }
+            BonusHand::SuitedBlackjack => hand.is_blackjack() && suited,
+            BonusHand::BlackjackInSuit(suit) => {
+                hand.is_blackjack() && cards.iter().all(|card| card.suit() == suit)
+            }
+        }
+    }
+}
+
+impl fmt::Display for BonusHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BonusHand::FiveCard21 => write!(f, "5-card 21"),
+            BonusHand::SuitedSixSevenEight => write!(f, "suited 6-7-8"),
+            BonusHand::SevenSevenSeven => write!(f, "7-7-7"),
+            BonusHand::SuitedBlackjack => write!(f, "suited blackjack"),
+            BonusHand::BlackjackInSuit(suit) => write!(f, "{} blackjack", suit.symbol()),
+        }
+    }
+}
+
+/// A bonus paid on a special hand, in place of the usual payout.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Bonus {
+    pub hand: BonusHand,
+    pub payout: Payout,
+}
+
+/// The rules of a table.
+///
+/// The default is a common six-deck shoe game: the dealer stands on soft 17
+/// and peeks for blackjack, the player may split to four hands and double
+/// after splitting but not resplit aces, there is no surrender, blackjack
+/// pays 3:2, insurance pays 2:1, there are no bonuses, and 75% of the shoe is dealt.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rules {
+    /// The number of decks in the shoe.
+    pub deck_count: u32,
+    /// Whether the dealer hits soft 17.
+    pub hits_soft_17: bool,
+    /// Whether the dealer takes a hole card and checks it for blackjack. If
+    /// not, the game is European no-hole-card (ENHC).
+    pub dealer_peeks: bool,
+    /// The most hands a player may split into, or 1 if splitting isn't
+    /// allowed.
+    pub max_split_hands: u32,
+    /// Whether split aces may be split again.
+    pub resplit_aces: bool,
+    /// Whether the player may double after splitting.
+    pub double_after_split: bool,
+    /// Whether the player may double on any number of cards, not just the
+    /// first two.
+    pub double_any_cards: bool,
+    /// Whether a player 21 wins even when the dealer also has 21. It still
+    /// loses to a dealer blackjack, unless the player has a blackjack too.
+    pub player_21_always_wins: bool,
+    /// The number of cards that wins a hand automatically if it hasn't
+    /// busted, if any. `Some(6)` is a six-card Charlie.
+    pub charlie: Option<usize>,
+    /// Whether the dealer wins hands that tie, including ties of blackjacks,
+    /// instead of pushing.
+    pub dealer_wins_ties: bool,
+    /// Whether a dealer total of 22 pushes every player hand still standing,
+    /// instead of busting. A player blackjack still wins.
+    pub push_on_dealer_22: bool,
+    /// When the player may surrender.
+    pub surrender: Surrender,
+    /// What a player blackjack pays.
+    pub blackjack_payout: Payout,
+    /// What insurance pays when the dealer has blackjack.
+    pub insurance_payout: Payout,
+    /// Bonuses for special hands.
+    pub bonuses: Vec<Bonus>,
+    /// The fraction of the shoe that is dealt before shuffling.
+    pub penetration: f64,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules {
+            deck_count: 6,
+            hits_soft_17: false,
+            dealer_peeks: true,
+            max_split_hands: 4,
+            resplit_aces: false,
+            double_after_split: true,
+            double_any_cards: false,
+            player_21_always_wins: false,
+            charlie: None,
+            dealer_wins_ties: false,
+            push_on_dealer_22: false,
+            surrender: Surrender::None,
+            blackjack_payout: Payout(3, 2),
+            insurance_payout: Payout(2, 1),
+            bonuses: Vec::new(),
+            penetration: 0.75,
+        }
+    }
+}
+
+impl Rules {
+    /// Returns the rules of Super Fun 21: a single deck where blackjack pays
+    /// even money except in diamonds, which pays 2:1, a player 21 always wins,
+    /// six cards that haven't busted win, and the player may double on any
+    /// number of cards, resplit aces, and surrender late.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::rules::Rules;
+    ///
+    /// let rules = Rules::super_fun_21();
+    /// assert_eq!(rules.to_string(), "1D H17 RSA DAS DANY 21W 6CC LS 1:1, pen 75%");
+    /// assert!(rules.validate().is_ok());
+    /// ```
+    pub fn super_fun_21() -> Rules {
+        Rules {
+            deck_count: 1,
+            hits_soft_17: true,
+            resplit_aces: true,
+            double_any_cards: true,
+            player_21_always_wins: true,
+            charlie: Some(6),
+            surrender: Surrender::Late,
+            blackjack_payout: Payout(1, 1),
+            bonuses: vec![Bonus {
+                hand: BonusHand::BlackjackInSuit(Suit::Diamonds),
+                payout: Payout(2, 1),
+            }],
+            ..Rules::default()
+        }
+    }
+
+    /// Checks that the rules make sense together.
+    ///
+    /// Returns the first `RulesError` if they can't be played as given, or
+    /// else any `RulesWarning`s about rules that have no effect or are
+    /// unusually bad for the player.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::rules::{Payout, Rules, RulesError, RulesWarning, Surrender};
+    ///
+    /// assert_eq!(Rules::default().validate(), Ok(vec![]));
+    ///
+    /// let enhc = Rules { dealer_peeks: false, surrender: Surrender::Late, ..Rules::default() };
+    /// assert_eq!(enhc.validate(), Err(RulesError::LateSurrenderWithoutPeek));
+    ///
+    /// let no_splits = Rules { max_split_hands: 1, ..Rules::default() };
+    /// assert_eq!(no_splits.validate(), Ok(vec![RulesWarning::DoubleAfterSplitWithoutSplitting]));
+    /// ```
+    pub fn validate(&self) -> Result<Vec<RulesWarning>, RulesError> {
+        if self.deck_count == 0 {
+            return Err(RulesError::NoDecks);
+        }
+        if !(self.penetration > 0.0 && self.penetration <= 1.0) {
+            return Err(RulesError::InvalidPenetration);
+        }
+        if self.blackjack_payout.1 == 0
+            || self.insurance_payout.1 == 0
+            || self.bonuses.iter().any(|b| b.payout.1 == 0)
+        {
+            return Err(RulesError::InvalidPayout);
+        }
+        if self.max_split_hands == 0 {
+            return Err(RulesError::NoHands);
+        }
+        if self.charlie.is_some_and(|count| count < 3) {
+            return Err(RulesError::InvalidCharlie);
+        }
+        if self.resplit_aces && self.max_split_hands < 3 {
+            return Err(RulesError::ResplitWithoutSplitting);
+        }
+        if self.surrender == Surrender::Late && !self.dealer_peeks {
+            return Err(RulesError::LateSurrenderWithoutPeek);
+        }
+
+        let mut warnings = Vec::new();
+        if self.double_after_split && self.max_split_hands == 1 {
+            warnings.push(RulesWarning::DoubleAfterSplitWithoutSplitting);
+        }
+        if self.blackjack_payout.0 < self.blackjack_payout.1 {
+            warnings.push(RulesWarning::BlackjackPaysLessThanEven);
+        }
+        Ok(warnings)
+    }
+
+    /// Returns whether `player` wins, loses, or pushes against the dealer's
+    /// finished hand.
+    ///
+    /// A blackjack counts as higher than any other 21. Surrender and insurance
+    /// are not considered.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::hand::BlackjackHand;
+    /// use blackjack::rules::{Outcome, Rules};
+    ///
+    /// let player: BlackjackHand = "T8".parse().unwrap();
+    /// let dealer: BlackjackHand = "T66".parse().unwrap();
+    /// let rules = Rules::default();
+    /// assert_eq!(rules.outcome(&player, &dealer), Outcome::Win);
+    ///
+    /// let rules = Rules { push_on_dealer_22: true, ..Rules::default() };
+    /// assert_eq!(rules.outcome(&player, &dealer), Outcome::Push);
+    ///
+    /// let rules = Rules { dealer_wins_ties: true, ..Rules::default() };
+    /// assert_eq!(rules.outcome(&player, &"99".parse().unwrap()), Outcome::Loss);
+    /// ```
+    pub fn outcome(&self, player: &BlackjackHand, dealer: &BlackjackHand) -> Outcome {
+        Reason::decide(self, player, dealer).outcome()
+    }
+
+    /// Checks that an insurance bet of `insurance` is allowed on a main bet of
+    /// `bet`, which it is if it is no more than half the main bet, rounded
+    /// down. The limit is the same whatever `insurance_payout` is.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::rules::{Payout, Rules};
+    ///
+    /// let rules = Rules::default();
+    /// assert!(rules.check_insurance(25, 10).is_ok());
+    /// assert_eq!(rules.check_insurance(25, 13).unwrap_err().max(), 12);
+    /// assert_eq!(rules.insurance_payout.winnings(10), 20);
+    ///
+    /// let rules = Rules { insurance_payout: Payout(3, 2), ..Rules::default() };
+    /// assert_eq!(rules.check_insurance(25, 13).unwrap_err().max(), 12);
+    /// assert_eq!(rules.insurance_payout.winnings(12), 18);
+    /// ```
+    pub fn check_insurance(&self, bet: u32, insurance: u32) -> Result<(), InsuranceError> {
+        let max = bet / 2;
+        if insurance <= max {
+            Ok(())
+        } else {
+            Err(InsuranceError { max })
+        }
+    }
+
+    /// Returns the best bonus payout that `player` qualifies for, if any.
+    ///
+    /// A bonus is paid only when the player's hand wins or pushes, in place
+    /// of the usual payout.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::hand::BlackjackHand;
+    /// use blackjack::rules::{Bonus, BonusHand, Payout, Rules};
+    ///
+    /// let rules = Rules {
+    ///     bonuses: vec![
+    ///         Bonus { hand: BonusHand::SevenSevenSeven, payout: Payout(3, 2) },
+    ///         Bonus { hand: BonusHand::SuitedSixSevenEight, payout: Payout(2, 1) },
+    ///     ],
+    ///     ..Rules::default()
+    /// };
+    /// let player: BlackjackHand = "7♥ 7♠ 7♦".parse().unwrap();
+    /// assert_eq!(rules.bonus(&player), Some(Payout(3, 2)));
+    /// let player: BlackjackHand = "6♥ 8♥ 7♥".parse().unwrap();
+    /// assert_eq!(rules.bonus(&player), Some(Payout(2, 1)));
+    /// let player: BlackjackHand = "6♥ 8♥ 7♠".parse().unwrap();
+    /// assert_eq!(rules.bonus(&player), None);
+    /// ```
+    pub fn bonus(&self, player: &BlackjackHand) -> Option<Payout> {
+        self.best_bonus(player).map(|bonus| bonus.payout)
+    }
+
+    pub(crate) fn best_bonus(&self, player: &BlackjackHand) -> Option<&Bonus> {
+        self.bonuses
+            .iter()
+            .filter(|bonus| bonus.hand.matches(player))
+            .max_by(|a, b| {
+                let (a, b) = (a.payout, b.payout);
+                (u64::from(a.0) * u64::from(b.1)).cmp(&(u64::from(b.0) * u64::from(a.1)))
+            })
+    }
+
+    /// Returns a description of each rule that differs between `self` and
+    /// `other`, in the form "name: old → new".
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::rules::{Payout, Rules};
+    ///
+    /// let rules = Rules::default();
+    /// let other = Rules { hits_soft_17: true, blackjack_payout: Payout(6, 5), ..rules.clone() };
+    /// assert_eq!(rules.diff(&other), ["soft 17: stands → hits", "blackjack pays: 3:2 → 6:5"]);
+    /// assert!(rules.diff(&rules).is_empty());
+    /// ```
+    pub fn diff(&self, other: &Rules) -> Vec<String> {
+        self.fields()
+            .into_iter()
+            .zip(other.fields())
+            .filter(|(old, new)| old.1 != new.1)
+            .map(|((name, old), (_, new))| format!("{}: {} → {}", name, old, new))
+            .collect()
+    }
+
+    fn bonus_summary(&self) -> String {
+        if self.bonuses.is_empty() {
+            return String::from("none");
+        }
+        let bonuses: Vec<String> = self
+            .bonuses
+            .iter()
+            .map(|bonus| format!("{} pays {}", bonus.hand, bonus.payout))
+            .collect();
+        bonuses.join(", ")
+    }
+
+    /// Returns the name and value of each rule, for `diff`.
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        let yes_no = |value| String::from(if value { "yes" } else { "no" });
+        let surrender = match self.surrender {
+            Surrender::None => "none",
+            Surrender::Late => "late",
+            Surrender::Early => "early",
+        };
+        vec![
+            ("decks", format!("{}", self.deck_count)),
+            (
+                "soft 17",
+                String::from(if self.hits_soft_17 { "hits" } else { "stands" }),
+            ),
+            ("dealer peeks", yes_no(self.dealer_peeks)),
+            ("split hands", format!("{}", self.max_split_hands)),
+            ("resplit aces", yes_no(self.resplit_aces)),
+            ("double after split", yes_no(self.double_after_split)),
+            ("double any cards", yes_no(self.double_any_cards)),
+            ("player 21 always wins", yes_no(self.player_21_always_wins)),
+            (
+                "charlie",
+                match self.charlie {
+                    Some(count) => format!("{} cards", count),
+                    None => String::from("none"),
+                },
+            ),
+            ("dealer wins ties", yes_no(self.dealer_wins_ties)),
+            ("push on dealer 22", yes_no(self.push_on_dealer_22)),
+            ("surrender", String::from(surrender)),
+            ("blackjack pays", format!("{}", self.blackjack_payout)),
+            ("insurance pays", format!("{}", self.insurance_payout)),
+            ("bonuses", self.bonus_summary()),
+            ("penetration", format!("{:.0}%", self.penetration * 100.0)),
+        ]
+    }
+}
+
+impl fmt::Display for Rules {
+    /// Formats the rules in the shorthand players use.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::rules::{Rules, Surrender};
+    ///
+    /// let rules = Rules {
+    ///     hits_soft_17: true,
+    ///     surrender: Surrender::Late,
+    ///     penetration: 0.8,
+    ///     ..Rules::default()
+    /// };
+    /// assert_eq!(rules.to_string(), "6D H17 DAS LS 3:2, pen 80%");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}D", self.deck_count)?;
+        write!(f, " {}", if self.hits_soft_17 { "H17" } else { "S17" })?;
+        if !self.dealer_peeks {
+            write!(f, " ENHC")?;
+        }
+        if self.resplit_aces {
+            write!(f, " RSA")?;
+        }
+        if self.double_after_split {
+            write!(f, " DAS")?;
+        }
+        if self.double_any_cards {
+            write!(f, " DANY")?;
+        }
+        if self.player_21_always_wins {
+            write!(f, " 21W")?;
+        }
+        if let Some(count) = self.charlie {
+            write!(f, " {}CC", count)?;
+        }
+        if self.dealer_wins_ties {
+            write!(f, " DWT")?;
+        }
+        if self.push_on_dealer_22 {
+            write!(f, " P22")?;
+        }
+        match self.surrender {
+            Surrender::None => {}
+            Surrender::Late => write!(f, " LS")?,
+            Surrender::Early => write!(f, " ES")?,
+        }
+        write!(
+            f,
+            " {}, pen {:.0}%",
+            self.blackjack_payout,
+            self.penetration * 100.0
+        )
+    }
+}
+
+/// The result of a player's hand against the dealer's.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    /// The player wins.
+    Win,
+    /// The dealer wins.
+    Loss,
+    /// Neither wins, and the bet is returned.
+    Push,
+}
+
+/// A reason that `Rules::validate` rejected a set of rules.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RulesError {
+    /// The shoe has no decks.
+    NoDecks,
+    /// The penetration isn't more than 0 and at most 1.
+    InvalidPenetration,
+    /// The blackjack, insurance, or a bonus payout divides by zero.
+    InvalidPayout,
+    /// The most split hands is zero, which leaves no hand to play.
+    NoHands,
+    /// A Charlie needs fewer than three cards, so every starting hand wins.
+    InvalidCharlie,
+    /// Aces may be resplit, but there can't be more than two split hands.
+    ResplitWithoutSplitting,
+    /// Late surrender happens after the dealer peeks, but the dealer doesn't.
+    LateSurrenderWithoutPeek,
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            RulesError::NoDecks => "the shoe has no decks",
+            RulesError::InvalidPenetration => "penetration must be more than 0% and at most 100%",
+            RulesError::InvalidPayout => "a payout divides by zero",
+            RulesError::NoHands => "maximum split hands must be at least 1",
+            RulesError::InvalidCharlie => "a Charlie must need at least three cards",
+            RulesError::ResplitWithoutSplitting => {
+                "aces can't be resplit with fewer than three split hands"
+            }
+            RulesError::LateSurrenderWithoutPeek => {
+                "late surrender needs the dealer to peek for blackjack"
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RulesError {}
+
+/// Something allowed but suspicious about a set of rules, found by
+/// `Rules::validate`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RulesWarning {
+    /// Doubling after splitting is allowed, but splitting isn't.
+    DoubleAfterSplitWithoutSplitting,
+    /// A blackjack pays less than the bet.
+    BlackjackPaysLessThanEven,
+}
+
+impl fmt::Display for RulesWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            RulesWarning::DoubleAfterSplitWithoutSplitting => {
+                "doubling after splitting has no effect without splitting"
+            }
+            RulesWarning::BlackjackPaysLessThanEven => "blackjack pays less than even money",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// The result of a failed `Rules::check_insurance`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InsuranceError {
+    max: u32,
+}
+
+impl InsuranceError {
+    /// Returns the largest insurance bet allowed.
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+}
+
+impl fmt::Display for InsuranceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "insurance can't be more than {}", self.max)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InsuranceError {}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    /// Parses a hand, for tests of the rules and of the modules built on
+    /// them.
+    pub(crate) fn hand(s: &str) -> BlackjackHand {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn insurance_limit_is_half_the_bet() {
+        let limit = |payout| {
+            let rules = Rules {
+                insurance_payout: payout,
+                ..Rules::default()
+            };
+            match rules.check_insurance(30, 30) {
+                Ok(()) => 30,
+                Err(err) => err.max(),
+            }
+        };
+        assert_eq!(limit(Payout(2, 1)), 15);
+        assert_eq!(limit(Payout(3, 2)), 15);
+        assert_eq!(limit(Payout(1, 1)), 15);
+        assert_eq!(limit(Payout(0, 1)), 15);
+        assert_eq!(Payout(3, 2).winnings(u32::MAX), u64::from(u32::MAX) * 3 / 2);
+    }
+
+    #[test]
+    fn default_summary() {
+        assert_eq!(Rules::default().to_string(), "6D S17 DAS 3:2, pen 75%");
+    }
+
+    #[test]
+    fn diff_lists_every_changed_rule() {
+        let rules = Rules::default();
+        let other = Rules {
+            deck_count: 2,
+            double_after_split: false,
+            surrender: Surrender::Early,
+            penetration: 0.6,
+            bonuses: vec![Bonus {
+                hand: BonusHand::FiveCard21,
+                payout: Payout(2, 1),
+            }],
+            ..rules.clone()
+        };
+        assert_eq!(
+            rules.diff(&other),
+            [
+                "decks: 6 → 2",
+                "double after split: yes → no",
+                "surrender: none → early",
+                "bonuses: none → 5-card 21 pays 2:1",
+                "penetration: 75% → 60%",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_resplitting_without_splits() {
+        let rules = Rules {
+            max_split_hands: 2,
+            resplit_aces: true,
+            ..Rules::default()
+        };
+        assert_eq!(rules.validate(), Err(RulesError::ResplitWithoutSplitting));
+        let rules = Rules {
+            max_split_hands: 3,
+            ..rules
+        };
+        assert_eq!(rules.validate(), Ok(vec![]));
+        assert_eq!(rules.to_string(), "6D S17 RSA DAS 3:2, pen 75%");
+    }
+
+    #[test]
+    fn outcomes() {
+        let rules = Rules {
+            dealer_wins_ties: true,
+            push_on_dealer_22: true,
+            ..Rules::default()
+        };
+        assert_eq!(rules.outcome(&hand("AK"), &hand("T66")), Outcome::Win);
+        assert_eq!(rules.outcome(&hand("T8"), &hand("T66")), Outcome::Push);
+        assert_eq!(rules.outcome(&hand("AK"), &hand("QA")), Outcome::Loss);
+        assert_eq!(rules.outcome(&hand("AK"), &hand("T65")), Outcome::Win);
+        assert_eq!(rules.outcome(&hand("T65"), &hand("AK")), Outcome::Loss);
+        assert_eq!(rules.outcome(&hand("TQ5"), &hand("T65")), Outcome::Loss);
+        assert_eq!(rules.outcome(&hand("T8"), &hand("T67")), Outcome::Win);
+        assert_eq!(rules.to_string(), "6D S17 DAS DWT P22 3:2, pen 75%");
+        assert_eq!(
+            Rules::default().outcome(&hand("AK"), &hand("QA")),
+            Outcome::Push
+        );
+    }
+
+    #[test]
+    fn bonus_hands() {
+        assert!(BonusHand::FiveCard21.matches(&hand("23457")));
+        assert!(BonusHand::FiveCard21.matches(&hand("A2A2A4")));
+        assert!(!BonusHand::FiveCard21.matches(&hand("T5A5")));
+        assert!(BonusHand::SuitedBlackjack.matches(&hand("A♠ K♠")));
+        assert!(!BonusHand::SuitedBlackjack.matches(&hand("A♠ K♥")));
+        assert!(!BonusHand::SevenSevenSeven.matches(&hand("777A")));
+    }
+
+    #[test]
+    fn super_fun_21_outcomes() {
+        let rules = Rules::super_fun_21();
+        assert_eq!(rules.outcome(&hand("T74"), &hand("T7A3")), Outcome::Win);
+        assert_eq!(rules.outcome(&hand("T74"), &hand("AK")), Outcome::Loss);
+        assert_eq!(rules.outcome(&hand("AK"), &hand("AK")), Outcome::Win);
+        assert_eq!(rules.outcome(&hand("A2A2A2"), &hand("T9")), Outcome::Win);
+        assert_eq!(rules.outcome(&hand("A2A2A"), &hand("T9")), Outcome::Loss);
+        assert_eq!(rules.bonus(&hand("A♦ K♦")), Some(Payout(2, 1)));
+        assert_eq!(rules.bonus(&hand("A♦ K♠")), None);
+    }
+}
@@ -0,0 +1,312 @@
+//! Settling a player's hand against the dealer's.
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::hand::BlackjackHand;
+use crate::rules::{BonusHand, Outcome, Payout, Rules, Surrender};
+
+/// Why a hand won, lost, or pushed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Reason {
+    /// The player busted.
+    PlayerBust,
+    /// The player surrendered.
+    Surrendered,
+    /// The player has 21, which always wins under the rules.
+    Player21,
+    /// The player reached the Charlie card count without busting.
+    Charlie,
+    /// The player has a blackjack and the dealer doesn't.
+    PlayerBlackjack,
+    /// The dealer has a blackjack and the player doesn't.
+    DealerBlackjack,
+    /// The dealer busted.
+    DealerBust,
+    /// The dealer busted with 22, which pushes under the rules.
+    DealerPush22,
+    /// The player's total is higher than the dealer's.
+    HigherTotal,
+    /// The player's total is lower than the dealer's.
+    LowerTotal,
+    /// The hands tie.
+    Tie,
+    /// The hands tie, and ties go to the dealer under the rules.
+    DealerWinsTie,
+}
+
+impl Reason {
+    /// Returns whether the player wins, loses, or pushes for this reason.
+    pub fn outcome(self) -> Outcome {
+        match self {
+            Reason::Player21
+            | Reason::Charlie
+            | Reason::PlayerBlackjack
+            | Reason::DealerBust
+            | Reason::HigherTotal => Outcome::Win,
+            Reason::PlayerBust
+            | Reason::Surrendered
+            | Reason::DealerBlackjack
+            | Reason::LowerTotal
+            | Reason::DealerWinsTie => Outcome::Loss,
+            Reason::DealerPush22 | Reason::Tie => Outcome::Push,
+        }
+    }
+
+    /// Returns the reason a finished player hand wins, loses, or pushes
+    /// against the dealer's finished hand.
+    pub(crate) fn decide(rules: &Rules, player: &BlackjackHand, dealer: &BlackjackHand) -> Reason {
+        if player.is_bust() {
+            return Reason::PlayerBust;
+        }
+        if player.is_blackjack() && !dealer.is_blackjack() {
+            return Reason::PlayerBlackjack;
+        }
+        if dealer.is_blackjack() && !player.is_blackjack() {
+            return Reason::DealerBlackjack;
+        }
+        if rules.player_21_always_wins && player.total() == 21 {
+            return Reason::Player21;
+        }
+        if rules.charlie.is_some_and(|count| player.len() >= count) {
+            return Reason::Charlie;
+        }
+        if dealer.is_bust() {
+            return if rules.push_on_dealer_22 && dealer.total() == 22 {
+                Reason::DealerPush22
+            } else {
+                Reason::DealerBust
+            };
+        }
+        match player.total().cmp(&dealer.total()) {
+            Ordering::Greater => Reason::HigherTotal,
+            Ordering::Less => Reason::LowerTotal,
+            Ordering::Equal if rules.dealer_wins_ties => Reason::DealerWinsTie,
+            Ordering::Equal => Reason::Tie,
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Reason::PlayerBust => "player busts",
+            Reason::Surrendered => "player surrenders",
+            Reason::Player21 => "player 21 always wins",
+            Reason::Charlie => "player Charlie",
+            Reason::PlayerBlackjack => "player blackjack",
+            Reason::DealerBlackjack => "dealer blackjack",
+            Reason::DealerBust => "dealer busts",
+            Reason::DealerPush22 => "dealer 22 pushes",
+            Reason::HigherTotal => "player total is higher",
+            Reason::LowerTotal => "dealer total is higher",
+            Reason::Tie => "tie",
+            Reason::DealerWinsTie => "dealer wins ties",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// How a player's hand was settled.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Settlement {
+    reason: Reason,
+    bonus: Option<BonusHand>,
+    net: i64,
+}
+
+impl Settlement {
+    /// Returns whether the player won, lost, or pushed.
+    pub fn outcome(&self) -> Outcome {
+        self.reason.outcome()
+    }
+
+    /// Returns why the player won, lost, or pushed.
+    pub fn reason(&self) -> Reason {
+        self.reason
+    }
+
+    /// Returns the bonus hand that was paid, if any.
+    pub fn bonus(&self) -> Option<BonusHand> {
+        self.bonus
+    }
+
+    /// Returns the amount the player won, or lost if negative.
+    pub fn net(&self) -> i64 {
+        self.net
+    }
+}
+
+impl Rules {
+    /// Settles a bet of `bet` on the player's finished hand against the
+    /// dealer's.
+    ///
+    /// A player blackjack is paid at `blackjack_payout`, but a 21 in a split
+    /// hand is paid at even money, since it isn't a blackjack. A bonus hand that
+    /// wins or pushes is paid its bonus instead of the usual payout.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::rules::{Outcome, Rules};
+    /// use blackjack::settlement::Reason;
+    ///
+    /// let rules = Rules::default();
+    /// let settlement = rules.settle(&"AK".parse().unwrap(), &"T9".parse().unwrap(), 10);
+    /// assert_eq!(settlement.outcome(), Outcome::Win);
+    /// assert_eq!(settlement.reason(), Reason::PlayerBlackjack);
+    /// assert_eq!(settlement.net(), 15);
+    ///
+    /// let settlement = rules.settle(&"T7".parse().unwrap(), &"T66".parse().unwrap(), 10);
+    /// assert_eq!(settlement.reason(), Reason::DealerBust);
+    /// assert_eq!(settlement.reason().to_string(), "dealer busts");
+    /// assert_eq!(settlement.net(), 10);
+    /// ```
+    pub fn settle(&self, player: &BlackjackHand, dealer: &BlackjackHand, bet: u32) -> Settlement {
+        let reason = Reason::decide(self, player, dealer);
+        let outcome = reason.outcome();
+        let bonus = self.best_bonus(player).filter(|_| outcome != Outcome::Loss);
+        let net = match (bonus, outcome) {
+            (Some(bonus), _) => net_winnings(bonus.payout, bet),
+            (None, Outcome::Win) if reason == Reason::PlayerBlackjack => {
+                net_winnings(self.blackjack_payout, bet)
+            }
+            (None, Outcome::Win) => i64::from(bet),
+            (None, Outcome::Push) => 0,
+            (None, Outcome::Loss) => -i64::from(bet),
+        };
+        Settlement {
+            reason,
+            bonus: bonus.map(|bonus| bonus.hand),
+            net,
+        }
+    }
+
+    /// Settles a bet of `bet` that the player surrendered, losing half of
+    /// it, or returns `None` if the rules don't offer surrender.
+    ///
+    /// Half of an odd bet is rounded in the house's favor, so the player gets
+    /// back half the bet rounded down.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::rules::{Rules, Surrender};
+    ///
+    /// let rules = Rules { surrender: Surrender::Late, ..Rules::default() };
+    /// assert_eq!(rules.surrender(25).unwrap().net(), -13);
+    /// assert!(Rules::default().surrender(25).is_none());
+    /// ```
+    pub fn surrender(&self, bet: u32) -> Option<Settlement> {
+        if self.surrender == Surrender::None {
+            return None;
+        }
+        Some(Settlement {
+            reason: Reason::Surrendered,
+            bonus: None,
+            net: -i64::from(bet - bet / 2),
+        })
+    }
+}
+
+/// Returns the winnings for a bet at `payout`, as a net amount.
+fn net_winnings(payout: Payout, bet: u32) -> i64 {
+    i64::try_from(payout.winnings(bet)).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card;
+    use crate::cards::Rank::*;
+    use crate::cards::Suit::*;
+    use crate::rules::tests::hand;
+    use crate::rules::Bonus;
+    use alloc::vec;
+
+    #[test]
+    fn blackjack_pays_when_21_always_wins() {
+        let rules = Rules {
+            player_21_always_wins: true,
+            ..Rules::default()
+        };
+        let settlement = rules.settle(&hand("AK"), &hand("T9"), 10);
+        assert_eq!(settlement.reason(), Reason::PlayerBlackjack);
+        assert_eq!(settlement.net(), 15);
+        assert_eq!(
+            rules.settle(&hand("AK"), &hand("AQ"), 10).reason(),
+            Reason::Player21
+        );
+        assert_eq!(
+            rules.settle(&hand("T65"), &hand("T7A3"), 10).reason(),
+            Reason::Player21
+        );
+    }
+
+    #[test]
+    fn dealer_blackjack_beats_21_and_charlie() {
+        let rules = Rules {
+            dealer_peeks: false,
+            player_21_always_wins: true,
+            charlie: Some(5),
+            ..Rules::default()
+        };
+        let reason = |player, dealer| rules.settle(&hand(player), &hand(dealer), 10).reason();
+        assert_eq!(reason("T74", "AK"), Reason::DealerBlackjack);
+        assert_eq!(reason("22223", "AK"), Reason::DealerBlackjack);
+        assert_eq!(reason("T74", "T7A3"), Reason::Player21);
+        assert_eq!(reason("22223", "T9"), Reason::Charlie);
+        assert_eq!(reason("AK", "AK"), Reason::Player21);
+    }
+
+    #[test]
+    fn split_21_pays_even_money() {
+        let rules = Rules {
+            bonuses: vec![Bonus {
+                hand: BonusHand::SuitedBlackjack,
+                payout: Payout(2, 1),
+            }],
+            ..Rules::default()
+        };
+        let mut split = BlackjackHand::split_from(card(Ace, Spades));
+        split.push(card(King, Spades));
+        let settlement = rules.settle(&split, &hand("T9"), 10);
+        assert_eq!(settlement.reason(), Reason::HigherTotal);
+        assert_eq!(settlement.bonus(), None);
+        assert_eq!(settlement.net(), 10);
+        assert_eq!(rules.settle(&split, &hand("AK"), 10).net(), -10);
+    }
+
+    #[test]
+    fn settlements() {
+        let rules = Rules {
+            bonuses: vec![Bonus {
+                hand: BonusHand::SevenSevenSeven,
+                payout: Payout(3, 1),
+            }],
+            ..Rules::default()
+        };
+        let settle = |player, dealer| rules.settle(&hand(player), &hand(dealer), 10);
+
+        assert_eq!(settle("T9", "AK").reason(), Reason::DealerBlackjack);
+        assert_eq!(settle("T9", "AK").net(), -10);
+        assert_eq!(settle("AK", "AK").reason(), Reason::Tie);
+        assert_eq!(settle("AK", "AK").net(), 0);
+        assert_eq!(settle("T9", "T8").reason(), Reason::HigherTotal);
+        assert_eq!(settle("TQ5", "T8").net(), -10);
+
+        let bonus = settle("777", "T8");
+        assert_eq!(bonus.bonus(), Some(BonusHand::SevenSevenSeven));
+        assert_eq!(bonus.net(), 30);
+        assert_eq!(settle("777", "AK").bonus(), None);
+
+        assert_eq!(rules.surrender(25), None);
+        let rules = Rules {
+            surrender: Surrender::Early,
+            ..rules
+        };
+        assert_eq!(rules.surrender(20).unwrap().net(), -10);
+        assert_eq!(rules.surrender(25).unwrap().outcome(), Outcome::Loss);
+    }
+}
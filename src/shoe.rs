@@ -0,0 +1,605 @@
+//! A dealing shoe holding several decks of cards.
+//!
+//! Like the `cards` module, nothing here depends on the rules of Blackjack.
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use rand::thread_rng;
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use crate::cards::RngShuffler;
+use crate::cards::{card, Card, Deck, Hand, Shuffler, ALL_RANKS, ALL_SUITS};
+
+/// A dealing shoe.
+///
+/// A shoe starts out holding one or more complete decks. Cards dealt from the
+/// shoe are returned to its discard tray once they are out of play, and go
+/// back into the shoe when it is shuffled.
+///
+/// As with `Deck`, the cards are ordered from the bottom to the top of the
+/// shoe, so drawing a card takes one off the end.
+///
+/// The shoe's `ReshufflePolicy` decides when it is due to be shuffled. It
+/// starts out as `Penetration(0.75)`.
+#[derive(Debug, Clone)]
+pub struct Shoe {
+    deck_count: usize,
+    cards: Vec<Card>,
+    discards: Vec<Card>,
+    dealt: Vec<Card>,
+    rounds_since_shuffle: u32,
+    reshuffle_policy: Arc<dyn ReshufflePolicy>,
+}
+
+impl Shoe {
+    /// Returns a shoe holding `deck_count` ordered decks, one on top of the
+    /// other.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::shoe::Shoe;
+    ///
+    /// let shoe = Shoe::new(6);
+    /// assert_eq!(shoe.deck_count(), 6);
+    /// assert_eq!(shoe.len(), 312);
+    /// ```
+    pub fn new(deck_count: usize) -> Shoe {
+        let mut cards = Vec::with_capacity(deck_count * 52);
+        for _ in 0..deck_count {
+            cards.extend(Deck::default().iter());
+        }
+        Shoe {
+            deck_count,
+            cards,
+            discards: Vec::with_capacity(deck_count * 52),
+            dealt: Vec::with_capacity(deck_count * 52),
+            rounds_since_shuffle: 0,
+            reshuffle_policy: Arc::new(Penetration(0.75)),
+        }
+    }
+
+    /// Returns a shuffled shoe holding `deck_count` decks.
+    #[cfg(feature = "std")]
+    pub fn shuffled(deck_count: usize) -> Shoe {
+        let mut shoe = Shoe::new(deck_count);
+        shoe.shuffle();
+        shoe
+    }
+
+    /// Returns the discards to the shoe and shuffles all of its cards.
+    ///
+    /// Cards that are still in play (held in hands) are not part of the
+    /// shuffle. They should be discarded before shuffling if the whole shoe is
+    /// to be reshuffled.
+    #[cfg(feature = "std")]
+    pub fn shuffle(&mut self) {
+        self.shuffle_with(&mut RngShuffler::new(thread_rng()));
+    }
+
+    /// Returns the discards to the shoe and shuffles all of its cards with the
+    /// given `Shuffler`.
+    pub fn shuffle_with<S: Shuffler + ?Sized>(&mut self, shuffler: &mut S) {
+        self.cards.append(&mut self.discards);
+        shuffler.shuffle(&mut self.cards);
+        self.dealt.clear();
+        self.rounds_since_shuffle = 0;
+    }
+
+    /// Replaces the policy that decides when the shoe is due to be shuffled.
+    pub fn set_reshuffle_policy<P: ReshufflePolicy + 'static>(&mut self, policy: P) {
+        self.reshuffle_policy = Arc::new(policy);
+    }
+
+    /// Records that a round has been played from the shoe.
+    pub fn end_round(&mut self) {
+        self.rounds_since_shuffle += 1;
+    }
+
+    /// Returns the number of rounds ended since the shoe was last shuffled.
+    pub fn rounds_since_shuffle(&self) -> u32 {
+        self.rounds_since_shuffle
+    }
+
+    /// Returns the fraction of the shoe's cards that are out of the shoe,
+    /// whether in play, burned, or discarded, from `0.0` to `1.0`.
+    ///
+    /// Extra cards discarded into the shoe don't count against it, so a shoe
+    /// holding more than `deck_count()` decks has a penetration of `0.0`.
+    pub fn penetration(&self) -> f64 {
+        let total = self.deck_count * 52;
+        if total == 0 {
+            0.0
+        } else {
+            total.saturating_sub(self.cards.len()) as f64 / total as f64
+        }
+    }
+
+    /// Returns `true` if the reshuffle policy says the shoe should be shuffled
+    /// before the next round.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::shoe::{EveryRounds, Shoe};
+    ///
+    /// let mut shoe = Shoe::shuffled(6);
+    /// shoe.set_reshuffle_policy(EveryRounds(2));
+    ///
+    /// shoe.end_round();
+    /// assert!(!shoe.needs_shuffle());
+    /// shoe.end_round();
+    /// assert!(shoe.needs_shuffle());
+    ///
+    /// shoe.shuffle();
+    /// assert!(!shoe.needs_shuffle());
+    /// ```
+    pub fn needs_shuffle(&self) -> bool {
+        self.reshuffle_policy.needs_shuffle(self)
+    }
+
+    /// Returns the number of decks the shoe was filled with.
+    pub fn deck_count(&self) -> usize {
+        self.deck_count
+    }
+
+    /// Returns count of cards remaining in the shoe.
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Returns `true` if the shoe contains no cards.
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Returns count of cards in the discard tray.
+    pub fn discard_count(&self) -> usize {
+        self.discards.len()
+    }
+
+    /// Removes the top card from the shoe and returns it, or `None` if no cards
+    /// remain.
+    pub fn pop(&mut self) -> Option<Card> {
+        let card = self.cards.pop()?;
+        self.dealt.push(card);
+        Some(card)
+    }
+
+    /// Returns the cards drawn with `pop` since the shoe was last shuffled, in
+    /// the order they were drawn.
+    ///
+    /// Burned cards are not included, since they may not have been shown.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::shoe::Shoe;
+    ///
+    /// let mut shoe = Shoe::new(1);
+    /// shoe.burn(1);
+    /// shoe.pop();
+    /// shoe.pop();
+    /// assert_eq!(shoe.dealt(), &[card(King, Spades), card(Queen, Spades)]);
+    /// ```
+    pub fn dealt(&self) -> &[Card] {
+        &self.dealt
+    }
+
+    /// Deals the first two cards of a round: one card to each player hand and
+    /// then the dealer, twice.
+    ///
+    /// The cards are added to the hands, so the hands can be reused from round
+    /// to round once they have been discarded. Returns `false` without dealing
+    /// anything if the shoe doesn't hold enough cards.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, Hand};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::shoe::Shoe;
+    ///
+    /// let mut shoe = Shoe::new(1);
+    /// let mut hands = vec![Hand::default(), Hand::default()];
+    /// let mut dealer = Hand::default();
+    /// assert!(shoe.deal_round(&mut hands, &mut dealer));
+    /// assert_eq!(hands[0][0], card(Ace, Spades));
+    /// assert_eq!(hands[1][0], card(King, Spades));
+    /// assert_eq!(dealer[0], card(Queen, Spades));
+    /// assert_eq!(dealer[1], card(Nine, Spades));
+    /// assert_eq!(shoe.len(), 46);
+    /// ```
+    pub fn deal_round(&mut self, hands: &mut [Hand], dealer: &mut Hand) -> bool {
+        let needed = 2 * (hands.len() + 1);
+        if self.cards.len() < needed {
+            return false;
+        }
+        let start = self.cards.len() - needed;
+        let mut cards = self.cards[start..].iter().rev();
+        for _ in 0..2 {
+            for hand in hands.iter_mut() {
+                hand.push(*cards.next().unwrap());
+            }
+            dealer.push(*cards.next().unwrap());
+        }
+        self.dealt.extend(self.cards[start..].iter().rev());
+        self.cards.truncate(start);
+        true
+    }
+
+    /// Burns up to `count` cards from the top of the shoe, moving them straight
+    /// to the discard tray, and returns the burned cards.
+    ///
+    /// Whether the burned cards are shown to the players is up to the caller;
+    /// a hidden burn should simply ignore the returned cards. Burned cards are
+    /// left out of `dealt`, and a `Composition` tracking the shoe should only
+    /// remove them if they were shown, since a hidden burn doesn't change the
+    /// odds of the next card.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::shoe::Shoe;
+    ///
+    /// let mut shoe = Shoe::new(1);
+    /// assert_eq!(shoe.burn(2), &[card(Ace, Spades), card(King, Spades)]);
+    /// assert_eq!(shoe.len(), 50);
+    /// assert_eq!(shoe.discard_count(), 2);
+    /// assert_eq!(shoe.pop(), Some(card(Queen, Spades)));
+    /// ```
+    pub fn burn(&mut self, count: usize) -> &[Card] {
+        let start = self.discards.len();
+        for _ in 0..count {
+            match self.cards.pop() {
+                Some(card) => self.discards.push(card),
+                None => break,
+            }
+        }
+        &self.discards[start..]
+    }
+
+    /// Puts a single card in the discard tray.
+    pub fn discard(&mut self, card: Card) {
+        self.discards.push(card);
+    }
+
+    /// Moves every card in a hand to the discard tray, leaving the hand empty.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Hand;
+    /// use blackjack::shoe::Shoe;
+    ///
+    /// let mut shoe = Shoe::new(1);
+    /// let mut hand = Hand::default();
+    /// hand.push(shoe.pop().unwrap());
+    /// hand.push(shoe.pop().unwrap());
+    ///
+    /// shoe.discard_hand(&mut hand);
+    /// assert!(hand.is_empty());
+    /// assert_eq!(shoe.len(), 50);
+    /// assert_eq!(shoe.discard_count(), 2);
+    /// ```
+    pub fn discard_hand(&mut self, hand: &mut Hand) {
+        self.discards.extend(hand.iter());
+        hand.clear();
+    }
+
+    /// Checks that the cards in the shoe, the discard tray, and the given
+    /// in-play hands add up to exactly `deck_count()` complete decks.
+    ///
+    /// Returns an `AuditError` listing the missing and duplicated cards if they
+    /// do not.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::{card, Hand};
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::shoe::Shoe;
+    ///
+    /// let mut shoe = Shoe::new(2);
+    /// let mut hand = Hand::default();
+    /// hand.push(shoe.pop().unwrap());
+    /// assert!(shoe.audit(&[&hand]).is_ok());
+    ///
+    /// // Forgetting about the hand loses a card.
+    /// let err = shoe.audit(&[]).unwrap_err();
+    /// assert_eq!(err.missing(), &[card(Ace, Spades)]);
+    /// assert!(err.duplicated().is_empty());
+    /// ```
+    pub fn audit(&self, in_play: &[&Hand]) -> Result<(), AuditError> {
+        let mut counts = [0usize; 52];
+        let in_play_cards = in_play.iter().flat_map(|hand| hand.iter());
+        for &card in self.cards.iter().chain(&self.discards).chain(in_play_cards) {
+            counts[card.index()] += 1;
+        }
+
+        let mut missing = Vec::new();
+        let mut duplicated = Vec::new();
+        for &suit in ALL_SUITS.iter() {
+            for &rank in ALL_RANKS.iter() {
+                let card = card(rank, suit);
+                let count = counts[card.index()];
+                for _ in count..self.deck_count {
+                    missing.push(card);
+                }
+                for _ in self.deck_count..count {
+                    duplicated.push(card);
+                }
+            }
+        }
+
+        if missing.is_empty() && duplicated.is_empty() {
+            Ok(())
+        } else {
+            Err(AuditError {
+                missing,
+                duplicated,
+            })
+        }
+    }
+}
+
+/// Decides when a shoe should be shuffled.
+///
+/// The shoe asks its policy between rounds, through `Shoe::needs_shuffle`.
+/// Policies must be `Send` and `Sync` so that a shoe can be moved to or shared
+/// with another thread.
+pub trait ReshufflePolicy: fmt::Debug + Send + Sync {
+    /// Returns `true` if `shoe` should be shuffled before the next round.
+    fn needs_shuffle(&self, shoe: &Shoe) -> bool;
+}
+
+/// Shuffle once the cut card comes out, which happens when no more than the
+/// given number of cards remain in the shoe.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CutCard(pub usize);
+
+impl ReshufflePolicy for CutCard {
+    fn needs_shuffle(&self, shoe: &Shoe) -> bool {
+        shoe.len() <= self.0
+    }
+}
+
+/// Shuffle once at least the given fraction of the shoe has been dealt.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Penetration(pub f64);
+
+impl ReshufflePolicy for Penetration {
+    fn needs_shuffle(&self, shoe: &Shoe) -> bool {
+        shoe.penetration() >= self.0
+    }
+}
+
+/// Shuffle after the given number of rounds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct EveryRounds(pub u32);
+
+impl ReshufflePolicy for EveryRounds {
+    fn needs_shuffle(&self, shoe: &Shoe) -> bool {
+        shoe.rounds_since_shuffle() >= self.0
+    }
+}
+
+/// A continuous shuffling machine, where the discards go back into the shoe
+/// and are shuffled after every round.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ContinuousShuffler;
+
+impl ReshufflePolicy for ContinuousShuffler {
+    fn needs_shuffle(&self, shoe: &Shoe) -> bool {
+        shoe.rounds_since_shuffle() >= 1
+    }
+}
+
+/// The result of a failed `Shoe::audit`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AuditError {
+    missing: Vec<Card>,
+    duplicated: Vec<Card>,
+}
+
+impl AuditError {
+    /// Returns the cards that should be present but are not, once per missing
+    /// copy.
+    pub fn missing(&self) -> &[Card] {
+        &self.missing
+    }
+
+    /// Returns the cards that are present too many times, once per extra copy.
+    pub fn duplicated(&self) -> &[Card] {
+        &self.duplicated
+    }
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "shoe audit failed:")?;
+        if !self.missing.is_empty() {
+            write!(f, " missing")?;
+            for card in &self.missing {
+                write!(f, " {}", card)?;
+            }
+        }
+        if !self.duplicated.is_empty() {
+            if !self.missing.is_empty() {
+                write!(f, ";")?;
+            }
+            write!(f, " duplicated")?;
+            for card in &self.duplicated {
+                write!(f, " {}", card)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AuditError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+    use crate::cards::Rank::*;
+    use crate::cards::Suit::*;
+
+    #[test]
+    fn audit_full_shoe() {
+        let mut shoe = Shoe::new(6);
+        assert_eq!(shoe.audit(&[]), Ok(()));
+
+        let mut hand = Hand::default();
+        for _ in 0..10 {
+            hand.push(shoe.pop().unwrap());
+        }
+        assert_eq!(shoe.audit(&[&hand]), Ok(()));
+
+        shoe.discard_hand(&mut hand);
+        assert_eq!(shoe.audit(&[&hand]), Ok(()));
+    }
+
+    #[test]
+    fn reshuffle_policies() {
+        let mut shoe = Shoe::new(1);
+        assert!(!shoe.needs_shuffle());
+        shoe.burn(39);
+        assert!(shoe.needs_shuffle());
+
+        shoe.set_reshuffle_policy(CutCard(10));
+        assert!(!shoe.needs_shuffle());
+        shoe.burn(3);
+        assert!(shoe.needs_shuffle());
+
+        shoe.set_reshuffle_policy(ContinuousShuffler);
+        shoe.shuffle_with(&mut ReverseShuffler);
+        assert_eq!(shoe.len(), 52);
+        assert!(!shoe.needs_shuffle());
+        shoe.end_round();
+        assert!(shoe.needs_shuffle());
+    }
+
+    #[test]
+    fn penetration_with_extra_cards() {
+        let mut shoe = Shoe::new(1);
+        shoe.discard(card(Two, Clubs));
+        shoe.shuffle_with(&mut ReverseShuffler);
+        assert_eq!(shoe.len(), 53);
+        assert_eq!(shoe.penetration(), 0.0);
+        assert!(!shoe.needs_shuffle());
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        let mut shoe = Shoe::new(1);
+        shoe.pop();
+        shoe.set_reshuffle_policy(CutCard(48));
+        let snapshot = shoe.clone();
+
+        shoe.burn(3);
+        shoe.pop();
+        assert_eq!(shoe.len(), 47);
+        assert!(shoe.needs_shuffle());
+        assert_eq!(snapshot.len(), 51);
+        assert_eq!(snapshot.discard_count(), 0);
+        assert_eq!(snapshot.dealt(), &[card(Ace, Spades)]);
+        assert!(!snapshot.needs_shuffle());
+    }
+
+    #[test]
+    fn shoe_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Shoe>();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn shoe_can_be_shared_between_threads() {
+        use std::sync::{Arc, Mutex};
+        use std::thread;
+
+        let shoe = Arc::new(Mutex::new(Shoe::new(1)));
+        let dealers: Vec<_> = (0..4)
+            .map(|_| {
+                let shoe = Arc::clone(&shoe);
+                thread::spawn(move || {
+                    for _ in 0..13 {
+                        shoe.lock().unwrap().pop().unwrap();
+                    }
+                })
+            })
+            .collect();
+        for dealer in dealers {
+            dealer.join().unwrap();
+        }
+        assert!(shoe.lock().unwrap().is_empty());
+    }
+
+    #[derive(Debug)]
+    struct ReverseShuffler;
+
+    impl Shuffler for ReverseShuffler {
+        fn shuffle(&mut self, cards: &mut [Card]) {
+            cards.reverse();
+        }
+    }
+
+    #[test]
+    fn audit_reports_missing_and_duplicated() {
+        let mut shoe = Shoe::new(1);
+        shoe.pop();
+        shoe.discard(card(Two, Clubs));
+
+        let err = shoe.audit(&[]).unwrap_err();
+        assert_eq!(err.missing(), &[card(Ace, Spades)]);
+        assert_eq!(err.duplicated(), &[card(Two, Clubs)]);
+        assert_eq!(
+            format!("{}", err),
+            "shoe audit failed: missing A♠; duplicated 2♣"
+        );
+    }
+
+    #[test]
+    fn deal_round_matches_popping() {
+        let mut shoe = Shoe::new(1);
+        let mut popped = Shoe::new(1);
+        let mut hands = [Hand::default(), Hand::default(), Hand::default()];
+        let mut dealer = Hand::default();
+        for _ in 0..6 {
+            assert!(shoe.deal_round(&mut hands, &mut dealer));
+            for i in 0..2 {
+                for hand in hands.iter() {
+                    assert_eq!(Some(hand[i]), popped.pop());
+                }
+                assert_eq!(Some(dealer[i]), popped.pop());
+            }
+            for hand in hands.iter_mut() {
+                shoe.discard_hand(hand);
+            }
+            shoe.discard_hand(&mut dealer);
+        }
+        assert_eq!(shoe.dealt(), popped.dealt());
+        assert!(shoe.audit(&[]).is_ok());
+
+        // Four cards are left, and a round needs eight.
+        assert!(!shoe.deal_round(&mut hands, &mut dealer));
+        assert_eq!(shoe.len(), 4);
+        assert!(dealer.is_empty());
+    }
+}
@@ -0,0 +1,628 @@
+//! Side bets, settled apart from the main bet.
+
+use core::fmt;
+
+use crate::cards::{Card, Deck, Rank, ALL_RANKS};
+use crate::composition::Composition;
+use crate::dealer::dealer_outcomes;
+use crate::hand::BlackjackHand;
+use crate::rules::Payout;
+
+/// How a side bet is decided.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SideBetResult {
+    /// The bet wins and pays the given payout.
+    Win(Payout),
+    /// The bet loses.
+    Loss,
+}
+
+/// A side bet.
+///
+/// A side bet is decided at one of two points in a round, and implements the
+/// hook for that point. Each hook returns `None` if the bet isn't decided
+/// yet.
+///
+/// Examples:
+///
+/// A house bet paying 11:1 when the player's first two cards are a pair:
+///
+/// ```
+/// use blackjack::cards::Card;
+/// use blackjack::hand::BlackjackHand;
+/// use blackjack::rules::Payout;
+/// use blackjack::side_bets::{settle, SideBet, SideBetResult};
+///
+/// #[derive(Debug)]
+/// struct Pairs;
+///
+/// impl SideBet for Pairs {
+///     fn name(&self) -> &'static str {
+///         "Pairs"
+///     }
+///
+///     fn on_deal(&self, player: &BlackjackHand, _upcard: Card) -> Option<SideBetResult> {
+///         if player[0].rank() == player[1].rank() {
+///             Some(SideBetResult::Win(Payout(11, 1)))
+///         } else {
+///             Some(SideBetResult::Loss)
+///         }
+///     }
+/// }
+///
+/// let player = "8♠ 8♥".parse().unwrap();
+/// let dealer = "T♣ 7♦".parse().unwrap();
+/// assert_eq!(settle(&Pairs, &player, &dealer), SideBetResult::Win(Payout(11, 1)));
+/// ```
+pub trait SideBet: fmt::Debug {
+    /// Returns the name of the bet.
+    fn name(&self) -> &'static str;
+
+    /// Called once the player's first two cards and the dealer's upcard have
+    /// been dealt.
+    ///
+    /// The built-in bets don't decide anything if `player` has fewer than two
+    /// cards.
+    fn on_deal(&self, _player: &BlackjackHand, _upcard: Card) -> Option<SideBetResult> {
+        None
+    }
+
+    /// Called once the dealer's hand is finished.
+    fn on_dealer_resolution(&self, _dealer: &BlackjackHand) -> Option<SideBetResult> {
+        None
+    }
+}
+
+/// Settles `bet` for the player's first two cards and the dealer's finished
+/// hand, whose first card is the upcard.
+///
+/// A bet that neither hook decides loses, and so does a bet on a player hand
+/// of fewer than two cards or an empty dealer hand, which can't have been
+/// dealt fully.
+pub fn settle<B: SideBet + ?Sized>(
+    bet: &B,
+    player: &BlackjackHand,
+    dealer: &BlackjackHand,
+) -> SideBetResult {
+    if player.len() < 2 || dealer.is_empty() {
+        return SideBetResult::Loss;
+    }
+    bet.on_deal(player, dealer[0])
+        .or_else(|| bet.on_dealer_resolution(dealer))
+        .unwrap_or(SideBetResult::Loss)
+}
+
+/// Bust It, which wins when the dealer busts and pays more the more cards the
+/// dealer busts with.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BustIt {
+    /// The payouts for the dealer busting with 3, 4, 5, 6, 7, and 8 or more
+    /// cards.
+    pub payouts: [Payout; 6],
+}
+
+impl Default for BustIt {
+    /// Returns a common pay table: 1:1, 2:1, 9:1, 50:1, 100:1, and 250:1.
+    fn default() -> BustIt {
+        BustIt {
+            payouts: [
+                Payout(1, 1),
+                Payout(2, 1),
+                Payout(9, 1),
+                Payout(50, 1),
+                Payout(100, 1),
+                Payout(250, 1),
+            ],
+        }
+    }
+}
+
+impl BustIt {
+    /// Returns the expected value of a one-unit bet when the dealer draws
+    /// from `composition`, upcard included.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::composition::Composition;
+    /// use blackjack::side_bets::BustIt;
+    ///
+    /// let ev = BustIt::default().expected_value(&Composition::new(6), true);
+    /// assert!((ev - -0.0219).abs() < 0.0001);
+    /// ```
+    pub fn expected_value(&self, composition: &Composition, hits_soft_17: bool) -> f64 {
+        ALL_RANKS
+            .iter()
+            .filter(|&&upcard| composition.count(upcard) > 0)
+            .map(|&upcard| {
+                let mut remaining = composition.clone();
+                let probability = composition.probability(upcard);
+                remaining.remove(upcard);
+                let outcomes = dealer_outcomes(upcard, &remaining, hits_soft_17);
+                let winnings: f64 = (3..=8)
+                    .map(|count| {
+                        let payout = self.payouts[count - 3];
+                        outcomes.bust_with_cards(count) * f64::from(payout.0) / f64::from(payout.1)
+                    })
+                    .sum();
+                probability * (winnings - (1.0 - outcomes.bust()))
+            })
+            .sum()
+    }
+}
+
+impl SideBet for BustIt {
+    fn name(&self) -> &'static str {
+        "Bust It"
+    }
+
+    fn on_dealer_resolution(&self, dealer: &BlackjackHand) -> Option<SideBetResult> {
+        if dealer.is_bust() {
+            Some(SideBetResult::Win(self.payouts[dealer.len().min(8) - 3]))
+        } else {
+            Some(SideBetResult::Loss)
+        }
+    }
+}
+
+/// Lucky Lucky, which pays on the total of the player's first two cards and
+/// the dealer's upcard.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LuckyLucky {
+    /// The payout for three sevens of one suit.
+    pub suited_777: Payout,
+    /// The payout for a six, seven, and eight of one suit.
+    pub suited_678: Payout,
+    /// The payout for three sevens of mixed suits.
+    pub unsuited_777: Payout,
+    /// The payout for a six, seven, and eight of mixed suits.
+    pub unsuited_678: Payout,
+    /// The payout for any other 21 with all three cards of one suit.
+    pub suited_21: Payout,
+    /// The payout for any other 21.
+    pub unsuited_21: Payout,
+    /// The payout for a total of 20.
+    pub total_20: Payout,
+    /// The payout for a total of 19.
+    pub total_19: Payout,
+}
+
+impl Default for LuckyLucky {
+    /// Returns a common pay table: suited 7-7-7 pays 200:1, suited 6-7-8
+    /// 100:1, 7-7-7 50:1, 6-7-8 30:1, suited 21 15:1, 21 3:1, and 19 or 20
+    /// 2:1.
+    fn default() -> LuckyLucky {
+        LuckyLucky {
+            suited_777: Payout(200, 1),
+            suited_678: Payout(100, 1),
+            unsuited_777: Payout(50, 1),
+            unsuited_678: Payout(30, 1),
+            suited_21: Payout(15, 1),
+            unsuited_21: Payout(3, 1),
+            total_20: Payout(2, 1),
+            total_19: Payout(2, 1),
+        }
+    }
+}
+
+impl SideBet for LuckyLucky {
+    fn name(&self) -> &'static str {
+        "Lucky Lucky"
+    }
+
+    fn on_deal(&self, player: &BlackjackHand, upcard: Card) -> Option<SideBetResult> {
+        if player.len() < 2 {
+            return None;
+        }
+        let cards = [player[0], player[1], upcard];
+        let mut hand = BlackjackHand::default();
+        for &card in cards.iter() {
+            hand.push(card);
+        }
+        let suited = cards.iter().all(|card| card.suit() == upcard.suit());
+        let mut ranks = [player[0].rank(), player[1].rank(), upcard.rank()];
+        ranks.sort();
+        let sevens = ranks == [Rank::Seven; 3];
+        let six_seven_eight = ranks == [Rank::Six, Rank::Seven, Rank::Eight];
+
+        let payout = match hand.total() {
+            21 if sevens && suited => self.suited_777,
+            21 if six_seven_eight && suited => self.suited_678,
+            21 if sevens => self.unsuited_777,
+            21 if six_seven_eight => self.unsuited_678,
+            21 if suited => self.suited_21,
+            21 => self.unsuited_21,
+            20 => self.total_20,
+            19 => self.total_19,
+            _ => return Some(SideBetResult::Loss),
+        };
+        Some(SideBetResult::Win(payout))
+    }
+}
+
+/// Match the Dealer, which pays for each of the player's first two cards that
+/// matches the rank of the dealer's upcard, and more if the suit matches too.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MatchTheDealer {
+    /// The payout for each card that matches the upcard's rank but not its
+    /// suit.
+    pub unsuited: Payout,
+    /// The payout for each card that matches the upcard's rank and suit.
+    pub suited: Payout,
+}
+
+impl Default for MatchTheDealer {
+    /// Returns a common six-deck pay table: 4:1 for a match and 11:1 for a
+    /// suited match.
+    fn default() -> MatchTheDealer {
+        MatchTheDealer {
+            unsuited: Payout(4, 1),
+            suited: Payout(11, 1),
+        }
+    }
+}
+
+impl MatchTheDealer {
+    /// Returns the expected value of a one-unit bet dealt from `deck_count`
+    /// full decks.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::side_bets::MatchTheDealer;
+    ///
+    /// let ev = MatchTheDealer::default().expected_value(6);
+    /// assert!((ev - -0.0406).abs() < 0.0001);
+    /// ```
+    pub fn expected_value(&self, deck_count: u32) -> f64 {
+        let decks = f64::from(deck_count);
+        // The cards left after the upcard is dealt, and the ones that match it.
+        let remaining = 52.0 * decks - 1.0;
+        let suited = decks - 1.0;
+        let unsuited = 3.0 * decks;
+        let ratio = |payout: Payout| f64::from(payout.0) / f64::from(payout.1);
+        // Each of the player's two cards is equally likely to be any of the
+        // remaining cards, so the winnings for each card can be added.
+        let winnings =
+            2.0 * (suited * ratio(self.suited) + unsuited * ratio(self.unsuited)) / remaining;
+        let others = remaining - suited - unsuited;
+        let no_match = others / remaining * (others - 1.0) / (remaining - 1.0);
+        winnings - no_match
+    }
+
+    fn payout(&self, card: Card, upcard: Card) -> Option<Payout> {
+        if card == upcard {
+            Some(self.suited)
+        } else if card.rank() == upcard.rank() {
+            Some(self.unsuited)
+        } else {
+            None
+        }
+    }
+}
+
+impl SideBet for MatchTheDealer {
+    fn name(&self) -> &'static str {
+        "Match the Dealer"
+    }
+
+    fn on_deal(&self, player: &BlackjackHand, upcard: Card) -> Option<SideBetResult> {
+        if player.len() < 2 {
+            return None;
+        }
+        let first = self.payout(player[0], upcard);
+        let second = self.payout(player[1], upcard);
+        let result = match (first, second) {
+            (Some(a), Some(b)) => SideBetResult::Win(Payout(a.0 * b.1 + b.0 * a.1, a.1 * b.1)),
+            (Some(payout), None) | (None, Some(payout)) => SideBetResult::Win(payout),
+            (None, None) => SideBetResult::Loss,
+        };
+        Some(result)
+    }
+}
+
+/// A side bet with a progressive jackpot, where part of every bet is added to
+/// a meter, and a hand meeting the hit condition wins the whole meter.
+///
+/// Amounts are in units of the bet, which is fixed for a progressive. Settling
+/// the bet doesn't change the meter, so each settled bet should be passed to
+/// `record` to update it.
+#[derive(Debug, Clone, Copy)]
+pub struct Progressive {
+    seed: f64,
+    increment: f64,
+    hits: fn(&BlackjackHand, Card) -> bool,
+    hit_probability: f64,
+    meter: f64,
+}
+
+impl Progressive {
+    /// Returns a progressive dealt from `deck_count` decks, with the meter
+    /// at `seed`.
+    ///
+    /// Each bet adds `increment` to the meter, and a hit resets it to `seed`.
+    /// `hits` decides from the player's first two cards and the dealer's
+    /// upcard whether the jackpot is hit, and the chance of a hit is worked
+    /// out from it, so the expected value always describes the bet that is
+    /// paid.
+    ///
+    /// Returns an `InvalidMeter` error if `seed` or `increment` is negative
+    /// or not finite.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::side_bets::Progressive;
+    ///
+    /// let bet = Progressive::new(10.0, 0.25, |player, _| player[0] == card(Ace, Spades), 6).unwrap();
+    /// assert!((bet.hit_probability() - 1.0 / 52.0).abs() < 1e-12);
+    ///
+    /// assert!(Progressive::new(-1.0, 0.25, |_, _| false, 6).is_err());
+    /// assert!(Progressive::new(10.0, f64::NAN, |_, _| false, 6).is_err());
+    /// ```
+    pub fn new(
+        seed: f64,
+        increment: f64,
+        hits: fn(&BlackjackHand, Card) -> bool,
+        deck_count: u32,
+    ) -> Result<Progressive, InvalidMeter> {
+        let valid = |amount: f64| amount.is_finite() && amount >= 0.0;
+        if !valid(seed) || !valid(increment) {
+            return Err(InvalidMeter);
+        }
+        Ok(Progressive {
+            seed,
+            increment,
+            hits,
+            hit_probability: hit_probability(hits, deck_count),
+            meter: seed,
+        })
+    }
+
+    /// Returns the amount the meter starts at, and resets to after a hit.
+    pub fn seed(&self) -> f64 {
+        self.seed
+    }
+
+    /// Returns the amount each bet adds to the meter.
+    pub fn increment(&self) -> f64 {
+        self.increment
+    }
+
+    /// Returns the chance that a hand hits the jackpot.
+    pub fn hit_probability(&self) -> f64 {
+        self.hit_probability
+    }
+
+    /// Returns the current jackpot.
+    pub fn meter(&self) -> f64 {
+        self.meter
+    }
+
+    /// Returns what a hit pays at the current meter, rounded down to a
+    /// hundredth of a unit.
+    ///
+    /// A meter too large to count in hundredths with a `u32` pays the
+    /// largest amount that can be.
+    pub fn payout(&self) -> Payout {
+        Payout((self.meter * 100.0).floor() as u32, 100)
+    }
+
+    /// Updates the meter for a settled bet: the bet adds to the meter, and a
+    /// hit resets it to the seed.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::rules::Payout;
+    /// use blackjack::side_bets::{settle, Progressive, SideBetResult};
+    ///
+    /// let mut bet = Progressive::new(1000.0, 0.25, |player, upcard| {
+    ///     player[0].rank() == Ace && player[1].rank() == Ace && upcard.rank() == Ace
+    /// }, 6).unwrap();
+    /// let result = settle(&bet, &"AA".parse().unwrap(), &"7♠ T♣".parse().unwrap());
+    /// assert_eq!(result, SideBetResult::Loss);
+    /// bet.record(result);
+    /// assert_eq!(bet.meter(), 1000.25);
+    ///
+    /// let result = settle(&bet, &"AA".parse().unwrap(), &"A♠ T♣".parse().unwrap());
+    /// assert_eq!(result, SideBetResult::Win(Payout(100025, 100)));
+    /// bet.record(result);
+    /// assert_eq!(bet.meter(), 1000.0);
+    /// ```
+    pub fn record(&mut self, result: SideBetResult) {
+        match result {
+            SideBetResult::Win(_) => self.meter = self.seed,
+            SideBetResult::Loss => self.meter += self.increment,
+        }
+    }
+
+    /// Returns the expected value of a one-unit bet when the jackpot is
+    /// `meter`.
+    pub fn expected_value_at(&self, meter: f64) -> f64 {
+        self.hit_probability * meter - 1.0
+    }
+
+    /// Returns the expected value of a one-unit bet at the current meter.
+    pub fn expected_value(&self) -> f64 {
+        self.expected_value_at(self.meter)
+    }
+
+    /// Returns the jackpot above which the bet has a positive expected value.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::side_bets::Progressive;
+    ///
+    /// let bet = Progressive::new(10.0, 0.25, |player, _| player[0] == card(Ace, Spades), 6).unwrap();
+    /// assert!((bet.break_even_meter() - 52.0).abs() < 1e-9);
+    /// assert!(bet.expected_value() < 0.0);
+    /// assert!(bet.expected_value_at(60.0) > 0.0);
+    /// ```
+    pub fn break_even_meter(&self) -> f64 {
+        1.0 / self.hit_probability
+    }
+}
+
+/// Returns the chance that `hits` is true for the player's first two cards
+/// and the dealer's upcard, dealt from `deck_count` decks.
+fn hit_probability(hits: fn(&BlackjackHand, Card) -> bool, deck_count: u32) -> f64 {
+    let deck = Deck::default();
+    let copies = f64::from(deck_count);
+    let total = 52.0 * copies;
+    let mut probability = 0.0;
+    for &first in deck.iter() {
+        for &second in deck.iter() {
+            let second_copies = copies - if second == first { 1.0 } else { 0.0 };
+            if second_copies <= 0.0 {
+                continue;
+            }
+            let mut player = BlackjackHand::default();
+            player.push(first);
+            player.push(second);
+            for &upcard in deck.iter() {
+                let upcard_copies = copies
+                    - [first, second]
+                        .iter()
+                        .filter(|&&card| card == upcard)
+                        .count() as f64;
+                if upcard_copies > 0.0 && hits(&player, upcard) {
+                    probability += copies / total * second_copies / (total - 1.0) * upcard_copies
+                        / (total - 2.0);
+                }
+            }
+        }
+    }
+    probability
+}
+
+/// The result of a `Progressive` with a negative or non-finite meter amount.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidMeter;
+
+impl fmt::Display for InvalidMeter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "meter amounts must be finite and not negative")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidMeter {}
+
+impl SideBet for Progressive {
+    fn name(&self) -> &'static str {
+        "Progressive"
+    }
+
+    fn on_deal(&self, player: &BlackjackHand, upcard: Card) -> Option<SideBetResult> {
+        if player.len() < 2 {
+            None
+        } else if (self.hits)(player, upcard) {
+            Some(SideBetResult::Win(self.payout()))
+        } else {
+            Some(SideBetResult::Loss)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::tests::hand;
+
+    #[test]
+    fn bust_it_pays_by_card_count() {
+        let bet = BustIt::default();
+        let player = hand("T9");
+        assert_eq!(
+            settle(&bet, &player, &hand("T6K")),
+            SideBetResult::Win(Payout(1, 1))
+        );
+        assert_eq!(
+            settle(&bet, &player, &hand("2223A4T")),
+            SideBetResult::Win(Payout(100, 1))
+        );
+        assert_eq!(settle(&bet, &player, &hand("T7")), SideBetResult::Loss);
+    }
+
+    #[test]
+    fn bust_it_house_edge() {
+        let ev = |deck_count, hits_soft_17| {
+            BustIt::default().expected_value(&Composition::new(deck_count), hits_soft_17)
+        };
+        assert!((ev(6, false) - -0.06329).abs() < 0.00001);
+        assert!((ev(6, true) - -0.02186).abs() < 0.00001);
+        assert!((ev(8, true) - -0.02023).abs() < 0.00001);
+        assert!((ev(1, false) - -0.09144).abs() < 0.00001);
+    }
+
+    #[test]
+    fn lucky_lucky_pays_on_three_card_totals() {
+        let bet = LuckyLucky::default();
+        let win = |player: &str, dealer: &str| settle(&bet, &hand(player), &hand(dealer));
+        assert_eq!(win("7♥ 7♥", "7♥ T♠"), SideBetResult::Win(Payout(200, 1)));
+        assert_eq!(win("8♣ 6♣", "7♣ T♠"), SideBetResult::Win(Payout(100, 1)));
+        assert_eq!(win("7♥ 7♠", "7♥ T♠"), SideBetResult::Win(Payout(50, 1)));
+        assert_eq!(win("A♦ K♦", "Q♦ 8♠"), SideBetResult::Win(Payout(15, 1)));
+        assert_eq!(win("A♦ A♥", "9♦ 8♠"), SideBetResult::Win(Payout(3, 1)));
+        assert_eq!(win("T♦ 5♥", "4♦ 8♠"), SideBetResult::Win(Payout(2, 1)));
+        assert_eq!(win("T♦ 5♥", "3♦ 8♠"), SideBetResult::Loss);
+    }
+
+    #[test]
+    fn short_hands_lose() {
+        let upcard = "7♠".parse().unwrap();
+        let short = hand("7♥");
+        assert_eq!(LuckyLucky::default().on_deal(&short, upcard), None);
+        assert_eq!(MatchTheDealer::default().on_deal(&short, upcard), None);
+        assert_eq!(
+            settle(&LuckyLucky::default(), &short, &hand("7♠ T♣")),
+            SideBetResult::Loss
+        );
+        assert_eq!(
+            settle(&BustIt::default(), &hand("T9"), &BlackjackHand::default()),
+            SideBetResult::Loss
+        );
+    }
+
+    #[test]
+    fn progressive_hit_probability_matches_its_predicate() {
+        let bet = Progressive::new(
+            1000.0,
+            0.25,
+            |player, upcard| {
+                player[0].rank() == Rank::Ace
+                    && player[1].rank() == Rank::Ace
+                    && upcard.rank() == Rank::Ace
+            },
+            6,
+        )
+        .unwrap();
+        let expected = 24.0 * 23.0 * 22.0 / (312.0 * 311.0 * 310.0);
+        assert!((bet.hit_probability() - expected).abs() < 1e-15);
+        assert_eq!(
+            Progressive::new(f64::INFINITY, 0.25, |_, _| true, 6).unwrap_err(),
+            InvalidMeter
+        );
+    }
+
+    #[test]
+    fn match_the_dealer_adds_both_matches() {
+        let bet = MatchTheDealer::default();
+        let win = |player: &str, dealer: &str| settle(&bet, &hand(player), &hand(dealer));
+        assert_eq!(win("9♥ 9♠", "9♠ 7♦"), SideBetResult::Win(Payout(15, 1)));
+        assert_eq!(win("9♥ 2♠", "9♠ 7♦"), SideBetResult::Win(Payout(4, 1)));
+        assert_eq!(win("T♥ 2♠", "9♠ 7♦"), SideBetResult::Loss);
+        // A single deck has no suited matches for the player's cards.
+        let single_deck = 2.0 * 3.0 * 4.0 / 51.0 - 48.0 / 51.0 * 47.0 / 50.0;
+        assert!((MatchTheDealer::default().expected_value(1) - single_deck).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,231 @@
+//! Basic strategy charts as constant tables.
+//!
+//! Each chart is a `StrategyTable` built at compile time, so looking up a
+//! decision is a couple of array reads.
+
+use crate::cards::Rank;
+use crate::hand::StrategyKey;
+
+/// A basic strategy decision, as printed in strategy charts.
+///
+/// Doubling and surrendering are only allowed on the first two cards, so
+/// some actions say what to do instead when they aren't allowed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Hit,
+    Stand,
+    /// Double if allowed, otherwise hit.
+    DoubleOrHit,
+    /// Double if allowed, otherwise stand.
+    DoubleOrStand,
+    Split,
+    /// Surrender if allowed, otherwise hit.
+    SurrenderOrHit,
+    /// Surrender if allowed, otherwise stand.
+    SurrenderOrStand,
+    /// Surrender if allowed, otherwise split.
+    SurrenderOrSplit,
+}
+
+const H: Action = Action::Hit;
+const S: Action = Action::Stand;
+const DH: Action = Action::DoubleOrHit;
+const DS: Action = Action::DoubleOrStand;
+const P: Action = Action::Split;
+const RH: Action = Action::SurrenderOrHit;
+
+/// The totals of the first rows of the hard and soft parts of a chart.
+const FIRST_HARD: u32 = 4;
+const FIRST_SOFT: u32 = 12;
+
+/// A basic strategy chart, with a column for each dealer upcard from two to
+/// ace.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct StrategyTable {
+    hard: [[Action; 10]; 18],
+    soft: [[Action; 10]; 10],
+    pairs: [[Action; 10]; 10],
+}
+
+impl StrategyTable {
+    /// Returns the chart's action for a hand against the dealer's upcard.
+    ///
+    /// Totals below the first row of the chart, such as a lone split card
+    /// before its second card is dealt, use the first row, since they always
+    /// hit. Panics if `key` is a hard total over 21, since a busted hand has
+    /// no decision to make.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::hand::{BlackjackHand, StrategyKey};
+    /// use blackjack::strategy::{Action, MULTI_DECK_H17, MULTI_DECK_S17};
+    ///
+    /// let hand: BlackjackHand = "T6".parse().unwrap();
+    /// assert_eq!(MULTI_DECK_S17.action(hand.strategy_key(), Ten), Action::SurrenderOrHit);
+    /// assert_eq!(MULTI_DECK_S17.action(StrategyKey::Pair(8), Ace), Action::Split);
+    /// assert_eq!(MULTI_DECK_H17.action(StrategyKey::Pair(8), Ace), Action::SurrenderOrSplit);
+    /// assert_eq!(MULTI_DECK_S17.action(StrategyKey::Soft(18), Two), Action::Stand);
+    /// assert_eq!(MULTI_DECK_H17.action(StrategyKey::Soft(18), Two), Action::DoubleOrStand);
+    /// ```
+    pub const fn action(&self, key: StrategyKey, upcard: Rank) -> Action {
+        let column = column(upcard);
+        match key {
+            StrategyKey::Pair(value) => self.pairs[value as usize - 1][column],
+            StrategyKey::Soft(total) => self.soft[row(total, FIRST_SOFT)][column],
+            StrategyKey::Hard(total) => self.hard[row(total, FIRST_HARD)][column],
+        }
+    }
+}
+
+/// Returns the chart row for a total, where `first` is the total of the
+/// first row.
+const fn row(total: u32, first: u32) -> usize {
+    if total < first {
+        0
+    } else {
+        (total - first) as usize
+    }
+}
+
+/// Returns the chart column for an upcard.
+const fn column(upcard: Rank) -> usize {
+    match upcard {
+        Rank::Ace => 9,
+        Rank::Jack | Rank::Queen | Rank::King => 8,
+        rank => rank as usize - 2,
+    }
+}
+
+/// Basic strategy for four to eight decks where the dealer stands on soft 17,
+/// doubling after splitting is allowed, and late surrender is offered.
+#[rustfmt::skip]
+pub const MULTI_DECK_S17: StrategyTable = StrategyTable {
+    //   2   3   4   5   6   7   8   9   T   A
+    hard: [
+        [H , H , H , H , H , H , H , H , H , H ], // 4
+        [H , H , H , H , H , H , H , H , H , H ], // 5
+        [H , H , H , H , H , H , H , H , H , H ], // 6
+        [H , H , H , H , H , H , H , H , H , H ], // 7
+        [H , H , H , H , H , H , H , H , H , H ], // 8
+        [H , DH, DH, DH, DH, H , H , H , H , H ], // 9
+        [DH, DH, DH, DH, DH, DH, DH, DH, H , H ], // 10
+        [DH, DH, DH, DH, DH, DH, DH, DH, DH, H ], // 11
+        [H , H , S , S , S , H , H , H , H , H ], // 12
+        [S , S , S , S , S , H , H , H , H , H ], // 13
+        [S , S , S , S , S , H , H , H , H , H ], // 14
+        [S , S , S , S , S , H , H , H , RH, H ], // 15
+        [S , S , S , S , S , H , H , RH, RH, RH], // 16
+        [S , S , S , S , S , S , S , S , S , S ], // 17
+        [S , S , S , S , S , S , S , S , S , S ], // 18
+        [S , S , S , S , S , S , S , S , S , S ], // 19
+        [S , S , S , S , S , S , S , S , S , S ], // 20
+        [S , S , S , S , S , S , S , S , S , S ], // 21
+    ],
+    //   2   3   4   5   6   7   8   9   T   A
+    soft: [
+        [H , H , H , H , H , H , H , H , H , H ], // 12
+        [H , H , H , DH, DH, H , H , H , H , H ], // 13
+        [H , H , H , DH, DH, H , H , H , H , H ], // 14
+        [H , H , DH, DH, DH, H , H , H , H , H ], // 15
+        [H , H , DH, DH, DH, H , H , H , H , H ], // 16
+        [H , DH, DH, DH, DH, H , H , H , H , H ], // 17
+        [S , DS, DS, DS, DS, S , S , H , H , H ], // 18
+        [S , S , S , S , S , S , S , S , S , S ], // 19
+        [S , S , S , S , S , S , S , S , S , S ], // 20
+        [S , S , S , S , S , S , S , S , S , S ], // 21
+    ],
+    //   2   3   4   5   6   7   8   9   T   A
+    pairs: [
+        [P , P , P , P , P , P , P , P , P , P ], // A,A
+        [P , P , P , P , P , P , H , H , H , H ], // 2,2
+        [P , P , P , P , P , P , H , H , H , H ], // 3,3
+        [H , H , H , P , P , H , H , H , H , H ], // 4,4
+        [DH, DH, DH, DH, DH, DH, DH, DH, H , H ], // 5,5
+        [P , P , P , P , P , H , H , H , H , H ], // 6,6
+        [P , P , P , P , P , P , H , H , H , H ], // 7,7
+        [P , P , P , P , P , P , P , P , P , P ], // 8,8
+        [P , P , P , P , P , S , P , P , S , S ], // 9,9
+        [S , S , S , S , S , S , S , S , S , S ], // T,T
+    ],
+};
+
+/// Basic strategy for the same game as `MULTI_DECK_S17`, but with the dealer
+/// hitting soft 17.
+pub const MULTI_DECK_H17: StrategyTable = hits_soft_17(MULTI_DECK_S17);
+
+/// Returns a multi-deck stand-on-soft-17 chart with the changes for the
+/// dealer hitting soft 17.
+const fn hits_soft_17(table: StrategyTable) -> StrategyTable {
+    let mut table = table;
+    let ace = 9;
+    table.hard[(11 - FIRST_HARD) as usize][ace] = DH;
+    table.hard[(15 - FIRST_HARD) as usize][ace] = RH;
+    table.hard[(17 - FIRST_HARD) as usize][ace] = Action::SurrenderOrStand;
+    table.soft[(18 - FIRST_SOFT) as usize][column(Rank::Two)] = DS;
+    table.soft[(19 - FIRST_SOFT) as usize][column(Rank::Six)] = DS;
+    table.pairs[8 - 1][ace] = Action::SurrenderOrSplit;
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::ALL_RANKS;
+
+    #[test]
+    fn h17_differs_in_six_cells() {
+        let keys = (4..=21)
+            .map(StrategyKey::Hard)
+            .chain((12..=21).map(StrategyKey::Soft))
+            .chain((1..=10).map(StrategyKey::Pair));
+        let upcards = ALL_RANKS
+            .iter()
+            .cloned()
+            .filter(|&rank| rank <= Rank::Ten || rank == Rank::Ace);
+        let mut differences = 0;
+        for key in keys {
+            for upcard in upcards.clone() {
+                if MULTI_DECK_S17.action(key, upcard) != MULTI_DECK_H17.action(key, upcard) {
+                    differences += 1;
+                }
+            }
+        }
+        assert_eq!(differences, 6);
+    }
+
+    #[test]
+    fn low_totals_use_the_first_row() {
+        for &upcard in [Rank::Two, Rank::Six, Rank::Ace].iter() {
+            assert_eq!(
+                MULTI_DECK_S17.action(StrategyKey::Soft(11), upcard),
+                Action::Hit
+            );
+            assert_eq!(
+                MULTI_DECK_S17.action(StrategyKey::Hard(2), upcard),
+                Action::Hit
+            );
+            assert_eq!(
+                MULTI_DECK_H17.action(StrategyKey::Hard(3), upcard),
+                Action::Hit
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn busted_hands_panic() {
+        MULTI_DECK_S17.action(StrategyKey::Hard(22), Rank::Two);
+    }
+
+    #[test]
+    fn face_cards_use_the_ten_column() {
+        for &rank in [Rank::Jack, Rank::Queen, Rank::King].iter() {
+            assert_eq!(
+                MULTI_DECK_S17.action(StrategyKey::Hard(16), rank),
+                MULTI_DECK_S17.action(StrategyKey::Hard(16), Rank::Ten)
+            );
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! SVG images of cards and hands.
+//!
+//! Requires the `svg` feature. The images are self-contained SVG documents,
+//! so GUI and web frontends can use them without a separate set of card
+//! assets.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::cards::{Card, Hand, Rank, Suit};
+
+/// Width of a card image, in pixels.
+pub const CARD_WIDTH: u32 = 100;
+
+/// Height of a card image, in pixels.
+pub const CARD_HEIGHT: u32 = 140;
+
+/// How far each card in a hand image is offset from the one before it.
+const HAND_OFFSET: u32 = 30;
+
+/// Returns an SVG image of the face of a card.
+///
+/// Examples:
+///
+/// ```
+/// use blackjack::cards::card;
+/// use blackjack::cards::Rank::*;
+/// use blackjack::cards::Suit::*;
+/// use blackjack::svg::card_svg;
+///
+/// let svg = card_svg(card(Ten, Hearts));
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains(">10<"));
+/// assert!(svg.contains("♥"));
+/// ```
+pub fn card_svg(card: Card) -> String {
+    let mut svg = String::new();
+    start_svg(&mut svg, CARD_WIDTH);
+    write_card(&mut svg, card, 0);
+    end_svg(&mut svg);
+    svg
+}
+
+/// Returns an SVG image of the back of a card.
+pub fn card_back_svg() -> String {
+    let mut svg = String::new();
+    start_svg(&mut svg, CARD_WIDTH);
+    write_card_back(&mut svg, 0);
+    end_svg(&mut svg);
+    svg
+}
+
+/// Returns an SVG image of a hand, with the cards fanned out from left to
+/// right in the order they were added.
+///
+/// Examples:
+///
+/// ```
+/// use blackjack::cards::{card, Hand};
+/// use blackjack::cards::Rank::*;
+/// use blackjack::cards::Suit::*;
+/// use blackjack::svg::hand_svg;
+///
+/// let mut hand = Hand::default();
+/// hand.push(card(Ace, Spades));
+/// hand.push(card(King, Diamonds));
+///
+/// let svg = hand_svg(&hand);
+/// assert!(svg.contains("width=\"130\""));
+/// assert!(svg.contains("♠"));
+/// assert!(svg.contains("♦"));
+/// ```
+pub fn hand_svg(hand: &Hand) -> String {
+    let mut svg = String::new();
+    let width = CARD_WIDTH + HAND_OFFSET * (hand.len().max(1) as u32 - 1);
+    start_svg(&mut svg, width);
+    for (i, &card) in hand.iter().enumerate() {
+        write_card(&mut svg, card, i as u32 * HAND_OFFSET);
+    }
+    end_svg(&mut svg);
+    svg
+}
+
+fn start_svg(svg: &mut String, width: u32) {
+    write!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\">",
+        w = width,
+        h = CARD_HEIGHT
+    )
+    .unwrap();
+}
+
+fn end_svg(svg: &mut String) {
+    svg.push_str("</svg>");
+}
+
+/// Writes the outline of a card whose left edge is at `x`.
+fn write_outline(svg: &mut String, x: u32, fill: &str) {
+    write!(
+        svg,
+        "<rect x=\"{}.5\" y=\"0.5\" width=\"{}\" height=\"{}\" rx=\"8\" \
+         fill=\"{}\" stroke=\"#333\"/>",
+        x,
+        CARD_WIDTH - 1,
+        CARD_HEIGHT - 1,
+        fill
+    )
+    .unwrap();
+}
+
+fn write_card(svg: &mut String, card: Card, x: u32) {
+    let color = suit_color(card.suit());
+    let rank = rank_label(card.rank());
+    let suit = card.suit().symbol();
+
+    svg.push_str("<g font-family=\"sans-serif\">");
+    write_outline(svg, x, "white");
+    // The corner index, repeated upside down in the opposite corner.
+    for &rotation in [0, 180].iter() {
+        write!(
+            svg,
+            "<g fill=\"{color}\" transform=\"rotate({rotation} {cx} {cy})\">\
+             <text x=\"{ix}\" y=\"24\" font-size=\"20\" text-anchor=\"middle\">{rank}</text>\
+             <text x=\"{ix}\" y=\"44\" font-size=\"18\" text-anchor=\"middle\">{suit}</text>\
+             </g>",
+            color = color,
+            rotation = rotation,
+            cx = x + CARD_WIDTH / 2,
+            cy = CARD_HEIGHT / 2,
+            ix = x + 14,
+            rank = rank,
+            suit = suit
+        )
+        .unwrap();
+    }
+    write!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" font-size=\"48\" text-anchor=\"middle\" \
+         dominant-baseline=\"central\" fill=\"{}\">{}</text>",
+        x + CARD_WIDTH / 2,
+        CARD_HEIGHT / 2,
+        color,
+        suit
+    )
+    .unwrap();
+    svg.push_str("</g>");
+}
+
+fn write_card_back(svg: &mut String, x: u32) {
+    write_outline(svg, x, "white");
+    write!(
+        svg,
+        "<rect x=\"{}\" y=\"6\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"#1f4e9c\"/>",
+        x + 6,
+        CARD_WIDTH - 12,
+        CARD_HEIGHT - 12
+    )
+    .unwrap();
+}
+
+fn suit_color(suit: Suit) -> &'static str {
+    match suit {
+        Suit::Diamonds | Suit::Hearts => "#c8102e",
+        Suit::Clubs | Suit::Spades => "#000",
+    }
+}
+
+/// Returns the rank as printed on a physical card's index.
+fn rank_label(rank: Rank) -> &'static str {
+    match rank {
+        Rank::Ten => "10",
+        rank => rank.symbol(),
+    }
+}
@@ -0,0 +1,77 @@
+//! Blackjack point values of cards.
+//!
+//! The `cards` module knows nothing about Blackjack, so the point values are
+//! provided by an extension trait instead. Other games can define their own
+//! value traits for `Rank` and `Card` in the same way.
+
+use crate::cards::{Card, Rank};
+
+/// The amount added to a hand's total when one of its aces counts as 11
+/// instead of 1.
+pub const SOFT_ACE_BONUS: u32 = 10;
+
+/// Blackjack point values for ranks and cards.
+pub trait BlackjackValue: Copy {
+    /// Returns the Blackjack point value.
+    ///
+    /// Number cards count their pip value, face cards count 10, and aces
+    /// count 1. An ace may instead count as 11 by adding `SOFT_ACE_BONUS`,
+    /// which is up to the code valuing the whole hand.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::value::BlackjackValue;
+    ///
+    /// assert_eq!(Two.blackjack_value(), 2);
+    /// assert_eq!(Nine.blackjack_value(), 9);
+    /// assert_eq!(Ten.blackjack_value(), 10);
+    /// assert_eq!(Jack.blackjack_value(), 10);
+    /// assert_eq!(Queen.blackjack_value(), 10);
+    /// assert_eq!(King.blackjack_value(), 10);
+    /// assert_eq!(Ace.blackjack_value(), 1);
+    /// ```
+    fn blackjack_value(self) -> u32;
+
+    /// Returns `true` for tens and face cards.
+    fn is_ten_value(self) -> bool {
+        self.blackjack_value() == 10
+    }
+
+    /// Returns `true` for aces.
+    fn is_ace(self) -> bool {
+        self.blackjack_value() == 1
+    }
+}
+
+impl BlackjackValue for Rank {
+    fn blackjack_value(self) -> u32 {
+        match self {
+            Rank::Ace => 1,
+            Rank::Jack | Rank::Queen | Rank::King => 10,
+            rank => rank as u32,
+        }
+    }
+}
+
+impl BlackjackValue for Card {
+    /// Returns the Blackjack point value of the card's rank.
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use blackjack::cards::card;
+    /// use blackjack::cards::Rank::*;
+    /// use blackjack::cards::Suit::*;
+    /// use blackjack::value::BlackjackValue;
+    ///
+    /// assert_eq!(card(Seven, Hearts).blackjack_value(), 7);
+    /// assert_eq!(card(King, Spades).blackjack_value(), 10);
+    /// assert!(card(Queen, Clubs).is_ten_value());
+    /// assert!(card(Ace, Diamonds).is_ace());
+    /// ```
+    fn blackjack_value(self) -> u32 {
+        self.rank().blackjack_value()
+    }
+}